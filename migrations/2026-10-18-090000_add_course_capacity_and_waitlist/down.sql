@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE waitlist_entries;
+
+ALTER TABLE "courses" DROP COLUMN "capacity";
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+ALTER TABLE "courses" ADD COLUMN "capacity" INTEGER CHECK ("capacity" >= 0);
+
+CREATE TABLE waitlist_entries (
+    id SERIAL PRIMARY KEY,
+    student_id INTEGER NOT NULL REFERENCES students (id),
+    course_id  INTEGER NOT NULL REFERENCES courses (id),
+    created_at timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(student_id, course_id)
+);
+
+CREATE INDEX waitlist_entries_course_id_created_at_idx ON waitlist_entries (course_id, created_at, id);
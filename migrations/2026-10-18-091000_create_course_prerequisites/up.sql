@@ -0,0 +1,7 @@
+-- Your SQL goes here
+CREATE TABLE course_prerequisites (
+    course_id       INTEGER NOT NULL REFERENCES courses (id),
+    prerequisite_id INTEGER NOT NULL REFERENCES courses (id),
+    PRIMARY KEY (course_id, prerequisite_id),
+    CHECK (course_id <> prerequisite_id)
+);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "students_courses" DROP COLUMN "status_changed_at";
+ALTER TABLE "students_courses" DROP COLUMN "enrolled_at";
+ALTER TABLE "students_courses" DROP COLUMN "status";
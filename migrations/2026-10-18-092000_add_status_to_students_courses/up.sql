@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE "students_courses" ADD COLUMN "status" VARCHAR NOT NULL DEFAULT 'enrolled'
+    CHECK ("status" IN ('enrolled', 'dropped', 'completed', 'failed'));
+ALTER TABLE "students_courses" ADD COLUMN "enrolled_at" timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP;
+ALTER TABLE "students_courses" ADD COLUMN "status_changed_at" timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "students_courses" DROP COLUMN "grade";
+
+ALTER TABLE "courses" DROP COLUMN "credits";
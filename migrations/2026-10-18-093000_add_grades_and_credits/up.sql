@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE "courses" ADD COLUMN "credits" INTEGER NOT NULL DEFAULT 1 CHECK ("credits" >= 0);
+
+ALTER TABLE "students_courses" ADD COLUMN "grade" VARCHAR
+    CHECK ("grade" IN ('A', 'A-', 'B+', 'B', 'B-', 'C+', 'C', 'C-', 'D+', 'D', 'D-', 'F'));
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "waitlist_entries" DROP COLUMN "offering_id";
+ALTER TABLE "students_courses" DROP COLUMN "offering_id";
+
+DROP TABLE course_offerings;
+DROP TABLE terms;
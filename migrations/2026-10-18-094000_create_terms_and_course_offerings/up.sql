@@ -0,0 +1,23 @@
+-- Your SQL goes here
+CREATE TABLE terms (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL UNIQUE,
+    starts_on DATE NOT NULL,
+    ends_on DATE NOT NULL,
+    registration_open BOOLEAN NOT NULL DEFAULT FALSE,
+    CHECK (ends_on >= starts_on)
+);
+
+CREATE TABLE course_offerings (
+    id SERIAL PRIMARY KEY,
+    course_id INTEGER NOT NULL REFERENCES courses (id),
+    term_id INTEGER NOT NULL REFERENCES terms (id),
+    section VARCHAR NOT NULL DEFAULT '001',
+    capacity INTEGER CHECK ("capacity" >= 0),
+    UNIQUE(course_id, term_id, section)
+);
+
+CREATE INDEX course_offerings_term_id_idx ON course_offerings (term_id);
+
+ALTER TABLE "students_courses" ADD COLUMN "offering_id" INTEGER REFERENCES course_offerings (id);
+ALTER TABLE "waitlist_entries" ADD COLUMN "offering_id" INTEGER REFERENCES course_offerings (id);
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TABLE instructors (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    email VARCHAR NOT NULL,
+    created_at timestamp,
+    updated_at timestamp,
+    UNIQUE(email)
+);
+
+CREATE TABLE course_instructors (
+    course_id INTEGER NOT NULL REFERENCES courses (id),
+    instructor_id INTEGER NOT NULL REFERENCES instructors (id),
+    PRIMARY KEY (course_id, instructor_id)
+);
+
+CREATE INDEX course_instructors_instructor_id_idx ON course_instructors (instructor_id);
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE course_meetings (
+    id SERIAL PRIMARY KEY,
+    course_id INTEGER NOT NULL REFERENCES courses (id),
+    day_of_week VARCHAR NOT NULL CHECK (day_of_week IN ('monday', 'tuesday', 'wednesday', 'thursday', 'friday', 'saturday', 'sunday')),
+    start_time TIME NOT NULL,
+    end_time TIME NOT NULL,
+    room VARCHAR,
+    CHECK (end_time > start_time)
+);
+
+CREATE INDEX course_meetings_course_id_idx ON course_meetings (course_id);
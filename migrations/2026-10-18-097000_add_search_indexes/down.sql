@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX courses_search_idx;
+DROP INDEX courses_course_desc_trgm_idx;
+DROP INDEX courses_name_trgm_idx;
+DROP INDEX students_phone_number_trgm_idx;
+DROP INDEX students_email_trgm_idx;
+
+DROP EXTENSION IF EXISTS pg_trgm;
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX students_email_trgm_idx ON students USING GIN (email gin_trgm_ops);
+CREATE INDEX students_phone_number_trgm_idx ON students USING GIN (phone_number gin_trgm_ops);
+CREATE INDEX courses_name_trgm_idx ON courses USING GIN (name gin_trgm_ops);
+CREATE INDEX courses_course_desc_trgm_idx ON courses USING GIN (course_desc gin_trgm_ops);
+CREATE INDEX courses_search_idx ON courses USING GIN (to_tsvector('simple', name || ' ' || coalesce(course_desc, '')));
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- keys issued before roles existed keep the full access they had
+ALTER TABLE api_keys ADD COLUMN role VARCHAR NOT NULL DEFAULT 'admin'
+    CHECK (role IN ('admin', 'registrar', 'instructor', 'student'));
+ALTER TABLE api_keys ALTER COLUMN role DROP DEFAULT;
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+CREATE TABLE audit_events (
+    id SERIAL PRIMARY KEY,
+    actor VARCHAR,
+    action VARCHAR NOT NULL CHECK (action IN ('create', 'update', 'delete')),
+    entity_type VARCHAR NOT NULL,
+    entity_id VARCHAR NOT NULL,
+    before JSONB,
+    after JSONB,
+    request_id VARCHAR,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX audit_events_entity_idx ON audit_events (entity_type, entity_id);
+CREATE INDEX audit_events_actor_idx ON audit_events (actor);
+CREATE INDEX audit_events_created_at_idx ON audit_events (created_at);
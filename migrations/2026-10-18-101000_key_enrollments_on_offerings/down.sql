@@ -0,0 +1,19 @@
+-- This file should undo anything in `up.sql`
+-- fails if a student has taken the same course in more than one offering
+ALTER TABLE waitlist_entries
+    DROP CONSTRAINT waitlist_entries_offering_id_fkey,
+    DROP CONSTRAINT waitlist_entries_student_id_offering_id_key,
+    ALTER COLUMN offering_id DROP NOT NULL,
+    ADD CONSTRAINT waitlist_entries_offering_id_fkey FOREIGN KEY (offering_id) REFERENCES course_offerings (id),
+    ADD CONSTRAINT waitlist_entries_student_id_course_id_key UNIQUE (student_id, course_id);
+
+DROP INDEX students_courses_enrolled_idx;
+
+ALTER TABLE students_courses
+    DROP CONSTRAINT students_courses_offering_id_fkey,
+    DROP CONSTRAINT students_courses_pkey,
+    ALTER COLUMN offering_id DROP NOT NULL,
+    ADD CONSTRAINT students_courses_offering_id_fkey FOREIGN KEY (offering_id) REFERENCES course_offerings (id),
+    ADD CONSTRAINT students_courses_pkey PRIMARY KEY (student_id, course_id);
+
+ALTER TABLE course_offerings DROP CONSTRAINT course_offerings_id_course_id_key;
@@ -0,0 +1,56 @@
+-- Your SQL goes here
+-- enrollments and waitlist entries made before terms existed move into a closed legacy term
+INSERT INTO terms (name, starts_on, ends_on, registration_open)
+SELECT 'legacy', LEAST(MIN(legacy.created_at)::date, CURRENT_DATE), CURRENT_DATE, FALSE
+FROM (
+    SELECT enrolled_at AS created_at FROM students_courses WHERE offering_id IS NULL
+    UNION ALL
+    SELECT created_at FROM waitlist_entries WHERE offering_id IS NULL
+) AS legacy
+HAVING COUNT(*) > 0
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO course_offerings (course_id, term_id, capacity)
+SELECT courses.id, terms.id, courses.capacity
+FROM courses
+JOIN terms ON terms.name = 'legacy'
+WHERE courses.id IN (
+    SELECT course_id FROM students_courses WHERE offering_id IS NULL
+    UNION
+    SELECT course_id FROM waitlist_entries WHERE offering_id IS NULL
+)
+ON CONFLICT (course_id, term_id, section) DO NOTHING;
+
+UPDATE students_courses SET offering_id = course_offerings.id
+FROM course_offerings
+JOIN terms ON terms.id = course_offerings.term_id AND terms.name = 'legacy'
+WHERE students_courses.offering_id IS NULL
+    AND course_offerings.course_id = students_courses.course_id
+    AND course_offerings.section = '001';
+
+UPDATE waitlist_entries SET offering_id = course_offerings.id
+FROM course_offerings
+JOIN terms ON terms.id = course_offerings.term_id AND terms.name = 'legacy'
+WHERE waitlist_entries.offering_id IS NULL
+    AND course_offerings.course_id = waitlist_entries.course_id
+    AND course_offerings.section = '001';
+
+-- lets enrollments reference an offering together with its course, so the two can never disagree
+ALTER TABLE course_offerings ADD CONSTRAINT course_offerings_id_course_id_key UNIQUE (id, course_id);
+
+ALTER TABLE students_courses
+    DROP CONSTRAINT students_courses_pkey,
+    DROP CONSTRAINT students_courses_offering_id_fkey,
+    ALTER COLUMN offering_id SET NOT NULL,
+    ADD CONSTRAINT students_courses_pkey PRIMARY KEY (student_id, offering_id),
+    ADD CONSTRAINT students_courses_offering_id_fkey FOREIGN KEY (offering_id, course_id) REFERENCES course_offerings (id, course_id);
+
+-- a course can be retaken in a later term, but only one section of it can be active at a time
+CREATE UNIQUE INDEX students_courses_enrolled_idx ON students_courses (student_id, course_id) WHERE status = 'enrolled';
+
+ALTER TABLE waitlist_entries
+    DROP CONSTRAINT waitlist_entries_student_id_course_id_key,
+    DROP CONSTRAINT waitlist_entries_offering_id_fkey,
+    ALTER COLUMN offering_id SET NOT NULL,
+    ADD CONSTRAINT waitlist_entries_student_id_offering_id_key UNIQUE (student_id, offering_id),
+    ADD CONSTRAINT waitlist_entries_offering_id_fkey FOREIGN KEY (offering_id, course_id) REFERENCES course_offerings (id, course_id);
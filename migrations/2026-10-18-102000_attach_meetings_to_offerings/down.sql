@@ -0,0 +1,12 @@
+-- This file should undo anything in `up.sql`
+-- sections of a course with the same slot collapse back into one meeting
+DELETE FROM course_meetings AS duplicate
+USING course_meetings AS kept
+WHERE duplicate.course_id = kept.course_id
+    AND duplicate.day_of_week = kept.day_of_week
+    AND duplicate.start_time = kept.start_time
+    AND duplicate.end_time = kept.end_time
+    AND duplicate.id > kept.id;
+
+DROP INDEX course_meetings_offering_id_idx;
+ALTER TABLE course_meetings DROP COLUMN offering_id;
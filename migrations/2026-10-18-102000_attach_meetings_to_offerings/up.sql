@@ -0,0 +1,31 @@
+-- Your SQL goes here
+-- courses that have meetings but were never offered get a section in the legacy term so no meeting is lost
+INSERT INTO terms (name, starts_on, ends_on, registration_open)
+SELECT 'legacy', CURRENT_DATE, CURRENT_DATE, FALSE
+WHERE EXISTS (SELECT 1 FROM course_meetings WHERE course_id NOT IN (SELECT course_id FROM course_offerings))
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO course_offerings (course_id, term_id, capacity)
+SELECT courses.id, terms.id, courses.capacity
+FROM courses
+JOIN terms ON terms.name = 'legacy'
+WHERE courses.id IN (SELECT course_id FROM course_meetings)
+    AND courses.id NOT IN (SELECT course_id FROM course_offerings)
+ON CONFLICT (course_id, term_id, section) DO NOTHING;
+
+ALTER TABLE course_meetings ADD COLUMN offering_id INTEGER;
+
+-- every section starts out with the meetings its course had
+INSERT INTO course_meetings (course_id, offering_id, day_of_week, start_time, end_time, room)
+SELECT course_meetings.course_id, course_offerings.id, course_meetings.day_of_week, course_meetings.start_time, course_meetings.end_time, course_meetings.room
+FROM course_meetings
+JOIN course_offerings ON course_offerings.course_id = course_meetings.course_id
+WHERE course_meetings.offering_id IS NULL;
+
+DELETE FROM course_meetings WHERE offering_id IS NULL;
+
+ALTER TABLE course_meetings
+    ALTER COLUMN offering_id SET NOT NULL,
+    ADD CONSTRAINT course_meetings_offering_id_fkey FOREIGN KEY (offering_id, course_id) REFERENCES course_offerings (id, course_id);
+
+CREATE INDEX course_meetings_offering_id_idx ON course_meetings (offering_id);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE api_keys DROP CONSTRAINT api_keys_role_check;
+ALTER TABLE api_keys ADD CONSTRAINT api_keys_role_check
+    CHECK (role IN ('admin', 'registrar', 'instructor', 'student'));
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- monitor keys can only scrape /metrics
+ALTER TABLE api_keys DROP CONSTRAINT api_keys_role_check;
+ALTER TABLE api_keys ADD CONSTRAINT api_keys_role_check
+    CHECK (role IN ('admin', 'registrar', 'instructor', 'student', 'monitor'));
@@ -0,0 +1,130 @@
+use actix_web::{get, Responder, Scope, web};
+
+use crate::api::audit::types::{AuditEventResponse, ListAuditEventsRequest};
+use crate::api::auth::Principal;
+use crate::api::errors::ApiError;
+use crate::api::pagination;
+use crate::api::pagination::PageResponse;
+use crate::api::policy;
+use crate::db;
+use crate::db::audit::db_functions;
+
+pub fn audit_api_scope() -> Scope {
+    web::scope("/audit")
+        .service(list_audit_events)
+}
+
+#[get("")]
+async fn list_audit_events(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<ListAuditEventsRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::ADMIN)?;
+    let filter = params.filter()?;
+    if filter.since.zip(filter.until).is_some_and(|(since, until)| since >= until) { return Err(ApiError::bad_field("until", "must be after since")); }
+    let (limit, offset) = pagination::page_bounds(params.limit, params.offset, params.cursor)?;
+    let cursor = params.cursor;
+    let (events, total) = db::run(&data, move |conn| {
+        let events = db_functions::list_audit_events(conn, &filter, limit, offset, cursor)?;
+        let total = db_functions::count_audit_events(conn, &filter)?;
+        Ok((events, total))
+    }).await?;
+    let next_cursor = pagination::next_cursor(&events.iter().map(|x| x.id).collect::<Vec<i32>>(), limit);
+    Ok(PageResponse { items: events.into_iter().map(AuditEventResponse::from).collect(), next_cursor, total })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::{App, test, web};
+    use actix_web::http::StatusCode;
+    use diesel::prelude::*;
+    use uuid::Uuid;
+
+    use crate::api::audit::handlers::audit_api_scope;
+    use crate::api::auth::{API_KEY_HEADER, AuthConfig, Authentication, hash_api_key};
+    use crate::api::auth::tests as auth_tests;
+    use crate::api::request_id::{REQUEST_ID_HEADER, RequestId};
+    use crate::api::students::handlers::student_api_scope;
+    use crate::api::students::handlers::tests as students_tests;
+    use crate::api::students::types::CreateStudentRequest;
+    use crate::db::api_keys::models::Role;
+    use crate::db::initialize_db_pool;
+    use crate::schema::api_keys;
+
+    #[actix_web::test]
+    async fn test_list_audit_events() {
+        let pool = initialize_db_pool();
+        let key = "test-api-key-audit";
+        let request_id = format!("audit-request-{}", Uuid::new_v4());
+        let request = CreateStudentRequest { email: String::from("audit_user_one@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(false, &mut pool.clone().get().unwrap(), request.clone().into());
+        diesel::delete(api_keys::table.filter(api_keys::name.eq("test_api_key_audit"))).execute(&mut pool.get().unwrap()).unwrap();
+        diesel::insert_into(api_keys::table)
+            .values((api_keys::name.eq("test_api_key_audit"), api_keys::key_hash.eq(hash_api_key(key)), api_keys::role.eq(Role::Admin)))
+            .execute(&mut pool.get().unwrap())
+            .unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap(Authentication::new(Arc::new(AuthConfig::default())))
+                .wrap(RequestId)
+                .service(student_api_scope())
+                .service(audit_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/students")
+            .insert_header((API_KEY_HEADER, key))
+            .insert_header((REQUEST_ID_HEADER, request_id.as_str()))
+            .set_json(request.clone())
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let student_id = resp["id"].as_i64().unwrap();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/audit?request_id={}&action=create", request_id))
+            .insert_header((API_KEY_HEADER, key))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["total"], 1);
+        assert_eq!(resp["items"][0]["actor"], "test_api_key_audit");
+        assert_eq!(resp["items"][0]["entity_type"], "student");
+        assert_eq!(resp["items"][0]["entity_id"], student_id.to_string());
+        assert_eq!(resp["items"][0]["after"]["email"], request.email);
+        let created_at = resp["items"][0]["created_at"].as_str().unwrap().replace(' ', "T");
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/audit?request_id={}&action=create&since={}", request_id, created_at))
+            .insert_header((API_KEY_HEADER, key))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["total"], 1);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/audit?request_id={}&action=create&until={}", request_id, created_at))
+            .insert_header((API_KEY_HEADER, key))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["total"], 0);
+
+        let req = test::TestRequest::get()
+            .uri("/audit?since=2026-10-18T12:00:00&until=2026-10-18T12:00:00")
+            .insert_header((API_KEY_HEADER, key))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(|req, srv| auth_tests::as_principal(req, srv, "registrar@example.com", Role::Registrar))
+                .service(audit_api_scope())
+        ).await;
+        let req = test::TestRequest::get().uri("/audit").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        diesel::delete(api_keys::table.filter(api_keys::name.eq("test_api_key_audit"))).execute(&mut pool.get().unwrap()).unwrap();
+    }
+}
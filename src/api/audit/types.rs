@@ -0,0 +1,68 @@
+use chrono::{Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::api::errors::ApiError;
+use crate::db::audit::models::{AuditAction, AuditEvent, AuditEventFilter};
+
+#[derive(Deserialize)]
+pub struct ListAuditEventsRequest {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    pub cursor: Option<i32>,
+    pub actor: Option<String>,
+    pub action: Option<AuditAction>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub request_id: Option<String>,
+    pub since: Option<NaiveDateTime>,
+    pub until: Option<NaiveDateTime>,
+}
+
+impl ListAuditEventsRequest {
+    // since and until are local times, like the timestamps in the response
+    pub fn filter(&self) -> Result<AuditEventFilter, ApiError> {
+        Ok(AuditEventFilter {
+            actor: self.actor.clone(),
+            action: self.action,
+            entity_type: self.entity_type.clone(),
+            entity_id: self.entity_id.clone(),
+            request_id: self.request_id.clone(),
+            since: to_utc("since", self.since)?,
+            until: to_utc("until", self.until)?,
+        })
+    }
+}
+
+fn to_utc(field: &str, value: Option<NaiveDateTime>) -> Result<Option<NaiveDateTime>, ApiError> {
+    value.map(|x| Local.from_local_datetime(&x).earliest().map(|x| x.naive_utc()).ok_or_else(|| ApiError::bad_field(field, "is not a valid local time"))).transpose()
+}
+
+#[derive(Serialize)]
+pub struct AuditEventResponse {
+    pub id: i32,
+    pub actor: Option<String>,
+    pub action: AuditAction,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub request_id: Option<String>,
+    pub created_at: String,
+}
+
+impl From<AuditEvent> for AuditEventResponse {
+    fn from(value: AuditEvent) -> Self {
+        Self {
+            id: value.id,
+            actor: value.actor,
+            action: value.action,
+            entity_type: value.entity_type,
+            entity_id: value.entity_id,
+            before: value.before,
+            after: value.after,
+            request_id: value.request_id,
+            created_at: Local.from_utc_datetime(&value.created_at).format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
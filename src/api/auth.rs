@@ -0,0 +1,381 @@
+use std::env;
+use std::fs;
+use std::future::{ready, Ready};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use actix_web::{Error, FromRequest, get, HttpMessage, HttpRequest, HttpResponse, Responder, Scope, web};
+use actix_web::body::EitherBody;
+use actix_web::dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::AUTHORIZATION;
+use dotenvy::dotenv;
+use futures_util::future::LocalBoxFuture;
+use jsonwebtoken::{Algorithm, decode, decode_header, DecodingKey, Validation};
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::api::errors::ApiError;
+use crate::api::policy;
+use crate::api::request_id;
+use crate::db;
+use crate::db::api_keys::db_functions;
+use crate::db::api_keys::models::Role;
+use crate::db::audit::context::{self as audit_context, AuditContext};
+use crate::db::DbPool;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+// probes from docker and load balancers come without credentials
+pub const PUBLIC_PATHS: &[&str] = &["/healthz", "/readyz"];
+
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMethod {
+    Jwt,
+    ApiKey,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Principal {
+    pub subject: String,
+    pub method: AuthMethod,
+    pub role: Role,
+}
+
+impl FromRequest for Principal {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(req.extensions().get::<Principal>().cloned().ok_or_else(|| unauthorized("missing credentials")))
+    }
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+    role: Role,
+}
+
+#[derive(Default)]
+pub struct AuthConfig {
+    pub jwt_secret: Option<String>,
+    pub jwks: Option<JwkSet>,
+    pub issuer: Option<String>,
+    pub audience: Option<String>,
+}
+
+impl AuthConfig {
+    pub fn from_env() -> Self {
+        dotenv().ok();
+        let jwks = env::var("JWKS_FILE").ok().map(|path| load_jwks(&path));
+        AuthConfig {
+            jwt_secret: env::var("JWT_SECRET").ok().filter(|x| !x.is_empty()),
+            jwks,
+            issuer: env::var("JWT_ISSUER").ok(),
+            audience: env::var("JWT_AUDIENCE").ok(),
+        }
+    }
+
+    pub fn verify_token(&self, token: &str) -> Result<Principal, ApiError> {
+        let header = decode_header(token).map_err(|_| unauthorized("malformed token"))?;
+        // the key is picked from the algorithm, so a token cannot get an RSA public key used as an HMAC secret
+        let key = match header.alg {
+            Algorithm::HS256 => self.jwt_secret.as_ref().map(|x| DecodingKey::from_secret(x.as_bytes())),
+            Algorithm::RS256 => self.find_jwk(header.kid.as_deref()).and_then(|x| DecodingKey::from_jwk(x).ok()),
+            _ => None,
+        }.ok_or_else(|| unauthorized("no key to verify the token"))?;
+        let mut validation = Validation::new(header.alg);
+        validation.set_required_spec_claims(&["exp", "sub"]);
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        let data = decode::<Claims>(token, &key, &validation).map_err(|error| match error.kind() {
+            ErrorKind::ExpiredSignature => unauthorized("token has expired"),
+            _ => unauthorized("invalid token"),
+        })?;
+        Ok(Principal { subject: data.claims.sub, method: AuthMethod::Jwt, role: data.claims.role })
+    }
+
+    fn find_jwk(&self, kid: Option<&str>) -> Option<&Jwk> {
+        let jwks = self.jwks.as_ref()?;
+        match kid {
+            Some(kid) => jwks.find(kid),
+            None if jwks.keys.len() == 1 => jwks.keys.first(),
+            None => None,
+        }
+    }
+}
+
+pub fn load_jwks(path: &str) -> JwkSet {
+    let contents = fs::read_to_string(path).unwrap_or_else(|_| panic!("JWKS file {} should be readable", path));
+    serde_json::from_str(&contents).expect("JWKS file should hold a valid JSON Web Key Set")
+}
+
+pub fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+fn unauthorized(reason: &str) -> ApiError {
+    ApiError::Unauthorized(reason.to_string())
+}
+
+async fn authenticate(req: &ServiceRequest, config: &AuthConfig) -> Result<Principal, ApiError> {
+    if let Some(value) = req.headers().get(AUTHORIZATION) {
+        let value = value.to_str().map_err(|_| unauthorized("malformed authorization header"))?;
+        let token = value.strip_prefix("Bearer ").ok_or_else(|| unauthorized("expected a bearer token"))?;
+        return config.verify_token(token.trim());
+    }
+    if let Some(value) = req.headers().get(API_KEY_HEADER) {
+        let key_hash = hash_api_key(value.to_str().map_err(|_| unauthorized("malformed api key"))?);
+        let pool = req.app_data::<web::Data<DbPool>>().ok_or(ApiError::InternalError)?;
+        let api_key = db::run(pool, move |conn| Ok(db_functions::get_active_api_key(conn, &key_hash)?)).await?;
+        let api_key = api_key.ok_or_else(|| unauthorized("invalid api key"))?;
+        return Ok(Principal { subject: api_key.name, method: AuthMethod::ApiKey, role: api_key.role });
+    }
+    Err(unauthorized("missing credentials"))
+}
+
+pub struct Authentication {
+    config: Arc<AuthConfig>,
+}
+
+impl Authentication {
+    pub fn new(config: Arc<AuthConfig>) -> Self {
+        Authentication { config }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Authentication
+    where
+        S: Service<ServiceRequest, Response=ServiceResponse<B>, Error=Error> + 'static,
+        S::Future: 'static,
+        B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = AuthenticationMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuthenticationMiddleware { service: Rc::new(service), config: self.config.clone() }))
+    }
+}
+
+pub struct AuthenticationMiddleware<S> {
+    service: Rc<S>,
+    config: Arc<AuthConfig>,
+}
+
+impl<S, B> Service<ServiceRequest> for AuthenticationMiddleware<S>
+    where
+        S: Service<ServiceRequest, Response=ServiceResponse<B>, Error=Error> + 'static,
+        S::Future: 'static,
+        B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let config = self.config.clone();
+        // checked inside the future so a rejection still carries the request id
+        Box::pin(async move {
+            if PUBLIC_PATHS.contains(&req.path()) {
+                return service.call(req).await.map(ServiceResponse::map_into_left_body);
+            }
+            match authenticate(&req, &config).await {
+                Ok(principal) => {
+                    if let Err(error) = policy::require_path_access(&principal, req.path()) {
+                        return Ok(req.error_response(error).map_into_right_body());
+                    }
+                    // every change made while handling the request is audited under this principal
+                    let context = AuditContext { actor: Some(principal.subject.clone()), request_id: request_id::current() };
+                    req.extensions_mut().insert(principal);
+                    audit_context::scope(context, service.call(req)).await.map(ServiceResponse::map_into_left_body)
+                }
+                Err(error) => Ok(req.error_response(error).map_into_right_body()),
+            }
+        })
+    }
+}
+
+pub fn auth_api_scope() -> Scope {
+    web::scope("/auth")
+        .service(get_principal)
+}
+
+#[get("/me")]
+async fn get_principal(principal: Principal) -> impl Responder {
+    HttpResponse::Ok().json(principal)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::sync::Arc;
+
+    use actix_web::{App, HttpMessage, test, web};
+    use actix_web::dev::{Service, ServiceRequest};
+    use actix_web::http::StatusCode;
+    use chrono::Utc;
+    use diesel::prelude::*;
+    use jsonwebtoken::{Algorithm, encode, EncodingKey, Header};
+    use serde_json::json;
+
+    use crate::api::auth::{API_KEY_HEADER, auth_api_scope, AuthConfig, Authentication, AuthMethod, hash_api_key, load_jwks, Principal};
+    use crate::api::metrics::handlers::metrics_api_scope;
+    use crate::db::api_keys::models::Role;
+    use crate::db::initialize_db_pool;
+    use crate::schema::api_keys;
+
+    const SECRET: &str = "test-secret";
+
+    // stands in for the authentication middleware in handler tests, used through App::wrap_fn
+    pub fn as_principal<S: Service<ServiceRequest>>(req: ServiceRequest, srv: &S, subject: &str, role: Role) -> S::Future {
+        req.extensions_mut().insert(Principal { subject: String::from(subject), method: AuthMethod::Jwt, role });
+        srv.call(req)
+    }
+
+    pub fn as_admin<S: Service<ServiceRequest>>(req: ServiceRequest, srv: &S) -> S::Future {
+        as_principal(req, srv, "admin@example.com", Role::Admin)
+    }
+
+    fn hs256_token(secret: &str, expires_in: i64) -> String {
+        let claims = json!({ "sub": "registrar@example.com", "role": "registrar", "exp": Utc::now().timestamp() + expires_in });
+        encode(&Header::new(Algorithm::HS256), &claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap()
+    }
+
+    fn rs256_token(kid: &str) -> String {
+        let claims = json!({ "sub": "service-account", "role": "admin", "exp": Utc::now().timestamp() + 600 });
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(String::from(kid));
+        let key = EncodingKey::from_rsa_pem(include_bytes!("../../test_fixtures/jwt_rs256_private.pem")).unwrap();
+        encode(&header, &claims, &key).unwrap()
+    }
+
+    fn config() -> AuthConfig {
+        AuthConfig {
+            jwt_secret: Some(String::from(SECRET)),
+            jwks: Some(load_jwks(concat!(env!("CARGO_MANIFEST_DIR"), "/test_fixtures/jwks.json"))),
+            ..Default::default()
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_verify_hs256_token() {
+        let principal = config().verify_token(&hs256_token(SECRET, 600)).unwrap();
+        assert_eq!("registrar@example.com", principal.subject);
+        assert_eq!(AuthMethod::Jwt, principal.method);
+        assert_eq!(Role::Registrar, principal.role);
+        assert!(config().verify_token(&hs256_token("other-secret", 600)).is_err());
+        assert!(config().verify_token(&hs256_token(SECRET, -600)).is_err());
+        assert!(AuthConfig::default().verify_token(&hs256_token(SECRET, 600)).is_err());
+        let without_role = json!({ "sub": "registrar@example.com", "exp": Utc::now().timestamp() + 600 });
+        let token = encode(&Header::new(Algorithm::HS256), &without_role, &EncodingKey::from_secret(SECRET.as_bytes())).unwrap();
+        assert!(config().verify_token(&token).is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_verify_rs256_token() {
+        assert_eq!("service-account", config().verify_token(&rs256_token("test-key-1")).unwrap().subject);
+        assert!(config().verify_token(&rs256_token("unknown-key")).is_err());
+        assert!(config().verify_token("not-a-token").is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_hash_api_key() {
+        assert_eq!("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824", hash_api_key("hello"));
+    }
+
+    #[actix_web::test]
+    async fn test_authentication_middleware() {
+        let pool = initialize_db_pool();
+        let key = "test-api-key-one";
+        diesel::delete(api_keys::table.filter(api_keys::name.eq("test_api_key_one"))).execute(&mut pool.get().unwrap()).unwrap();
+        diesel::insert_into(api_keys::table)
+            .values((api_keys::name.eq("test_api_key_one"), api_keys::key_hash.eq(hash_api_key(key)), api_keys::role.eq(Role::Instructor)))
+            .execute(&mut pool.get().unwrap())
+            .unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap(Authentication::new(Arc::new(config())))
+                .service(auth_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get().uri("/auth/me").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(resp.headers().get("www-authenticate").unwrap(), "Bearer");
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["code"], "unauthorized");
+
+        let req = test::TestRequest::get()
+            .uri("/auth/me")
+            .insert_header(("Authorization", format!("Bearer {}", hs256_token(SECRET, 600))))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, json!({ "subject": "registrar@example.com", "method": "jwt", "role": "registrar" }));
+
+        let req = test::TestRequest::get()
+            .uri("/auth/me")
+            .insert_header((API_KEY_HEADER, key))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, json!({ "subject": "test_api_key_one", "method": "api_key", "role": "instructor" }));
+
+        let req = test::TestRequest::get()
+            .uri("/auth/me")
+            .insert_header((API_KEY_HEADER, "wrong-key"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        diesel::delete(api_keys::table.filter(api_keys::name.eq("test_api_key_one"))).execute(&mut pool.get().unwrap()).unwrap();
+    }
+
+    #[actix_web::test]
+    async fn test_monitor_key_only_reaches_metrics() {
+        let pool = initialize_db_pool();
+        let key = "test-api-key-monitor";
+        diesel::delete(api_keys::table.filter(api_keys::name.eq("test_api_key_monitor"))).execute(&mut pool.get().unwrap()).unwrap();
+        diesel::insert_into(api_keys::table)
+            .values((api_keys::name.eq("test_api_key_monitor"), api_keys::key_hash.eq(hash_api_key(key)), api_keys::role.eq(Role::Monitor)))
+            .execute(&mut pool.get().unwrap())
+            .unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap(Authentication::new(Arc::new(config())))
+                .service(auth_api_scope())
+                .service(metrics_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get()
+            .uri("/auth/me")
+            .insert_header((API_KEY_HEADER, key))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::get()
+            .uri("/metrics")
+            .insert_header((API_KEY_HEADER, key))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        diesel::delete(api_keys::table.filter(api_keys::name.eq("test_api_key_monitor"))).execute(&mut pool.get().unwrap()).unwrap();
+    }
+}
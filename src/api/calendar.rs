@@ -0,0 +1,169 @@
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::db::course_meetings::models::DayOfWeek;
+
+const PRODUCT_ID: &str = "-//rust_crud_app_course_management//schedule//EN";
+const MAX_LINE_OCTETS: usize = 75;
+
+pub struct WeeklyEvent {
+    pub uid: String,
+    pub summary: String,
+    pub location: Option<String>,
+    pub day_of_week: DayOfWeek,
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+    pub starts_on: NaiveDate,
+    pub ends_on: NaiveDate,
+}
+
+pub fn render(events: &[WeeklyEvent], stamp: NaiveDateTime) -> String {
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        format!("PRODID:{}", PRODUCT_ID),
+        String::from("CALSCALE:GREGORIAN"),
+    ];
+    for event in events {
+        let first = first_occurrence(event.starts_on, event.day_of_week);
+        // a term can end before the weekday first comes round, such an event never takes place
+        if first > event.ends_on { continue; }
+        let rule = format!("FREQ=WEEKLY;BYDAY={};UNTIL={}", ical_day(event.day_of_week), format_local(event.ends_on.and_hms_opt(23, 59, 59).unwrap()));
+        lines.push(String::from("BEGIN:VEVENT"));
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}Z", format_local(stamp)));
+        lines.push(format!("DTSTART:{}", format_local(first.and_time(event.start_time))));
+        lines.push(format!("DTEND:{}", format_local(first.and_time(event.end_time))));
+        lines.push(format!("RRULE:{}", rule));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(location) = &event.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        lines.push(String::from("END:VEVENT"));
+    }
+    lines.push(String::from("END:VCALENDAR"));
+    lines.iter().map(|line| fold_line(line) + "\r\n").collect()
+}
+
+pub fn first_occurrence(from: NaiveDate, day_of_week: DayOfWeek) -> NaiveDate {
+    let target = weekday(day_of_week).num_days_from_monday();
+    let current = from.weekday().num_days_from_monday();
+    from.checked_add_days(Days::new(((7 + target - current) % 7) as u64)).unwrap()
+}
+
+fn weekday(day_of_week: DayOfWeek) -> Weekday {
+    match day_of_week {
+        DayOfWeek::Monday => Weekday::Mon,
+        DayOfWeek::Tuesday => Weekday::Tue,
+        DayOfWeek::Wednesday => Weekday::Wed,
+        DayOfWeek::Thursday => Weekday::Thu,
+        DayOfWeek::Friday => Weekday::Fri,
+        DayOfWeek::Saturday => Weekday::Sat,
+        DayOfWeek::Sunday => Weekday::Sun,
+    }
+}
+
+fn ical_day(day_of_week: DayOfWeek) -> &'static str {
+    match day_of_week {
+        DayOfWeek::Monday => "MO",
+        DayOfWeek::Tuesday => "TU",
+        DayOfWeek::Wednesday => "WE",
+        DayOfWeek::Thursday => "TH",
+        DayOfWeek::Friday => "FR",
+        DayOfWeek::Saturday => "SA",
+        DayOfWeek::Sunday => "SU",
+    }
+}
+
+// meeting times carry no time zone, so events use floating local times
+fn format_local(value: NaiveDateTime) -> String {
+    value.format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn escape_text(value: &str) -> String {
+    value.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime};
+
+    use crate::api::calendar::{first_occurrence, fold_line, render, WeeklyEvent};
+    use crate::db::course_meetings::models::DayOfWeek;
+
+    #[test]
+    fn test_first_occurrence() {
+        let tuesday = NaiveDate::from_ymd_opt(2026, 9, 1).unwrap();
+        assert_eq!(first_occurrence(tuesday, DayOfWeek::Tuesday), tuesday);
+        assert_eq!(first_occurrence(tuesday, DayOfWeek::Monday), NaiveDate::from_ymd_opt(2026, 9, 7).unwrap());
+        assert_eq!(first_occurrence(tuesday, DayOfWeek::Friday), NaiveDate::from_ymd_opt(2026, 9, 4).unwrap());
+    }
+
+    #[test]
+    fn test_render_weekly_event() {
+        let event = WeeklyEvent {
+            uid: String::from("meeting-1@example.com"),
+            summary: String::from("algebra, linear"),
+            location: Some(String::from("B12")),
+            day_of_week: DayOfWeek::Monday,
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+            starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+            ends_on: NaiveDate::from_ymd_opt(2026, 12, 18).unwrap(),
+        };
+        let stamp = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_opt(9, 30, 0).unwrap();
+        let calendar = render(&[event], stamp);
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(calendar.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert!(calendar.contains("DTSTAMP:20261018T093000Z\r\n"));
+        assert!(calendar.contains("DTSTART:20260907T090000\r\nDTEND:20260907T103000\r\n"));
+        assert!(calendar.contains("RRULE:FREQ=WEEKLY;BYDAY=MO;UNTIL=20261218T235959\r\n"));
+        assert!(calendar.contains("SUMMARY:algebra\\, linear\r\n"));
+    }
+
+    #[test]
+    fn test_render_skips_event_after_term_end() {
+        let event = WeeklyEvent {
+            uid: String::from("meeting-2@example.com"),
+            summary: String::from("geometry"),
+            location: None,
+            day_of_week: DayOfWeek::Friday,
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+            starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+            ends_on: NaiveDate::from_ymd_opt(2026, 9, 3).unwrap(),
+        };
+        let stamp = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_opt(9, 30, 0).unwrap();
+        let calendar = render(&[event], stamp);
+
+        assert!(!calendar.contains("BEGIN:VEVENT"));
+        assert!(calendar.ends_with("CALSCALE:GREGORIAN\r\nEND:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn test_fold_long_lines() {
+        let folded = fold_line(&format!("SUMMARY:{}", "a".repeat(100)));
+        let lines = folded.split("\r\n").collect::<Vec<&str>>();
+        assert_eq!(75, lines[0].len());
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(folded.replace("\r\n ", ""), format!("SUMMARY:{}", "a".repeat(100)));
+    }
+}
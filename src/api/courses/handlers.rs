@@ -1,42 +1,305 @@
-use actix_web::{get, post, Responder, Scope, web};
+use actix_multipart::Multipart;
+use actix_web::{delete, get, HttpResponse, post, Responder, Scope, web};
+use diesel::{Connection, PgConnection};
 
-use crate::api::courses::types::{CourseResponse, CreateCourseRequest, GetCourseRequest};
-use crate::api::errors::ApiError;
+use crate::api::auth::Principal;
+use crate::api::courses::types::{AddPrerequisiteRequest, AssignInstructorRequest, CourseDetailResponse, CourseMeetingResponse, CourseResponse, CreateCourseMeetingRequest, CreateCourseRequest, DeleteCourseByIdRequest, DeleteCourseRequest, GetCourseRequest, GetCourseStudentsRequest, ListCoursesRequest};
+use crate::api::errors::{ApiError, FieldError};
+use crate::api::exports;
+use crate::api::exports::ExportRequest;
+use crate::api::imports;
+use crate::api::imports::ImportRequest;
+use crate::api::instructors::types::InstructorResponse;
+use crate::api::pagination;
+use crate::api::policy;
+use crate::api::pagination::PageResponse;
+use crate::api::students_courses::types::{CourseStudentFields, EnrolledStudentResponse};
 use crate::db;
+use crate::db::course_meetings::db_functions as course_meetings_db_functions;
+use crate::db::course_meetings::models::NewCourseMeeting;
+use crate::db::course_offerings::db_functions as course_offerings_db_functions;
+use crate::db::course_prerequisites::db_functions as course_prerequisites_db_functions;
 use crate::db::courses::db_functions;
-use crate::db::courses::models::NewCourse;
+use crate::db::courses::models::{Course, CourseSortField, NewCourse};
+use crate::db::instructors::db_functions as instructors_db_functions;
+use crate::db::students::db_functions as students_db_functions;
+use crate::db::students_courses::db_functions as students_courses_db_functions;
+use crate::db::students_courses::models::EnrollmentStatus;
 use crate::schema::courses::name;
 
 pub fn courses_api_scope() -> Scope {
     web::scope("/courses")
         .service(get_course)
+        .service(list_courses)
+        .service(export_courses)
+        .service(get_course_by_id)
+        .service(get_course_students)
         .service(create_course)
+        .service(import_courses)
+        .service(delete_course)
+        .service(delete_course_by_id)
+        .service(get_course_prerequisites)
+        .service(add_course_prerequisite)
+        .service(delete_course_prerequisite)
+        .service(assign_course_instructor)
+        .service(unassign_course_instructor)
+        .service(get_course_meetings)
+        .service(create_course_meeting)
+        .service(delete_course_meeting)
 }
 
 #[get("")]
-async fn get_course(data: web::Data<db::DbPool>, params: web::Query<GetCourseRequest>) -> Result<impl Responder, ApiError> {
-    let mut connection = data.get().unwrap();
-    let course = db_functions::get_course(&mut connection, &params.name)?;
-    Ok(CourseResponse::from(course))
+async fn get_course(data: web::Data<db::DbPool>, _principal: Principal, params: web::Query<GetCourseRequest>) -> Result<impl Responder, ApiError> {
+    let detail = db::run(&data, move |conn| {
+        let course = db_functions::get_course(conn, &params.name)?;
+        let instructors = instructors_db_functions::get_instructors_for_course(conn, &course)?;
+        Ok((course, instructors))
+    }).await?;
+    Ok(CourseDetailResponse::from(detail))
+}
+
+#[get("/list")]
+async fn list_courses(data: web::Data<db::DbPool>, _principal: Principal, params: web::Query<ListCoursesRequest>) -> Result<impl Responder, ApiError> {
+    let (limit, offset) = pagination::page_bounds(params.limit, params.offset, params.cursor)?;
+    if params.cursor.is_some() && params.sort != CourseSortField::Id { return Err(ApiError::bad_field("cursor", "cursor pagination requires sort=id")); }
+    let (sort, cursor) = (params.sort, params.cursor);
+    let (courses, total) = db::run(&data, move |conn| {
+        let courses = db_functions::list_courses(conn, sort, limit, offset, cursor)?;
+        let total = db_functions::count_courses(conn)?;
+        Ok((courses, total))
+    }).await?;
+    let next_cursor = match sort {
+        CourseSortField::Id => pagination::next_cursor(&courses.iter().map(|x| x.id).collect::<Vec<i32>>(), limit),
+        _ => None,
+    };
+    Ok(PageResponse { items: courses.into_iter().map(CourseResponse::from).collect(), next_cursor, total })
+}
+
+#[get("/export")]
+async fn export_courses(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<ExportRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    if params.course_id.is_some() { return Err(ApiError::bad_field("course_id", "filter is not supported for courses")); }
+    let student_id = params.student_id;
+    if let Some(student_id) = student_id {
+        db::run(&data, move |conn| Ok(students_db_functions::get_student_by_id(conn, student_id)?)).await?;
+    }
+    Ok(exports::stream_export(data.get_ref().clone(), params.format, "courses", &["id", "name", "course_desc", "capacity", "credits"], move |conn, after_id, limit| {
+        let courses = db_functions::list_courses_for_export(conn, student_id, after_id, limit)?;
+        Ok(courses.into_iter().map(|x| (x.id, CourseResponse::from(x))).collect())
+    }))
+}
+
+#[get("/{id}")]
+async fn get_course_by_id(data: web::Data<db::DbPool>, _principal: Principal, path: web::Path<i32>) -> Result<impl Responder, ApiError> {
+    let detail = db::run(&data, move |conn| {
+        let course = db_functions::get_course_by_id(conn, path.into_inner())?;
+        let instructors = instructors_db_functions::get_instructors_for_course(conn, &course)?;
+        Ok((course, instructors))
+    }).await?;
+    Ok(CourseDetailResponse::from(detail))
+}
+
+#[get("/{id}/students")]
+async fn get_course_students(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>, params: web::Query<GetCourseStudentsRequest>) -> Result<impl Responder, ApiError> {
+    let status = params.status;
+    let enrollments = db::run(&data, move |conn| {
+        let course = db_functions::get_course_by_id(conn, path.into_inner())?;
+        policy::require_roster_access(conn, &principal, &course)?;
+        Ok(students_courses_db_functions::get_enrollments_for_course(conn, &course, status)?)
+    }).await?;
+    match params.fields {
+        Some(CourseStudentFields::Email) => Ok(HttpResponse::Ok().json(enrollments.into_iter().map(|(_, x)| x.email).collect::<Vec<String>>())),
+        None => Ok(HttpResponse::Ok().json(enrollments.into_iter().map(EnrolledStudentResponse::from).collect::<Vec<EnrolledStudentResponse>>())),
+    }
 }
 
 #[post("")]
-async fn create_course(data: web::Data<db::DbPool>, req: web::Json<CreateCourseRequest>) -> Result<impl Responder, ApiError> {
-    let mut connection = data.get().unwrap();
-    let course = db_functions::create_course(&mut connection, NewCourse::from(req.0))?;
+async fn create_course(data: web::Data<db::DbPool>, principal: Principal, req: web::Json<CreateCourseRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let errors = validate_new_course(&req);
+    if !errors.is_empty() { return Err(ApiError::BadClientData(errors)); }
+    let course = db::run(&data, move |conn| Ok(db_functions::create_course(conn, NewCourse::from(req.0))?)).await?;
     Ok(CourseResponse::from(course))
 }
 
+#[post("/import")]
+async fn import_courses(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<ImportRequest>, payload: Multipart) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let bytes = imports::read_csv_upload(payload).await?;
+    let rows = imports::parse_csv::<CreateCourseRequest>(&bytes)?;
+    let mode = params.mode;
+    db::run(&data, move |conn| {
+        imports::run_import(conn, mode, rows, "name", validate_new_course, |conn, row| {
+            db_functions::create_course(conn, NewCourse::from(row)).map(|x| x.id)
+        })
+    }).await
+}
+
+fn validate_new_course(req: &CreateCourseRequest) -> Vec<FieldError> {
+    let mut errors = vec![];
+    if req.name.trim().is_empty() { errors.push(FieldError::new("name", "must not be empty")); }
+    if req.capacity.is_some_and(|capacity| capacity < 0) { errors.push(FieldError::new("capacity", "must not be negative")); }
+    if req.credits.is_some_and(|credits| credits < 0) { errors.push(FieldError::new("credits", "must not be negative")); }
+    errors
+}
+
+#[delete("")]
+async fn delete_course(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<DeleteCourseRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::ADMIN)?;
+    db::run(&data, move |conn| {
+        conn.transaction::<_, ApiError, _>(|conn| {
+            let course = db_functions::get_course(conn, &params.name)?;
+            remove_course(conn, &course, params.cascade)
+        })
+    }).await?;
+    Ok(HttpResponse::Ok().body("course deleted successfully"))
+}
+
+#[delete("/{id}")]
+async fn delete_course_by_id(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>, params: web::Query<DeleteCourseByIdRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::ADMIN)?;
+    db::run(&data, move |conn| {
+        conn.transaction::<_, ApiError, _>(|conn| {
+            let course = db_functions::get_course_by_id(conn, path.into_inner())?;
+            remove_course(conn, &course, params.cascade)
+        })
+    }).await?;
+    Ok(HttpResponse::Ok().body("course deleted successfully"))
+}
+
+fn remove_course(conn: &mut PgConnection, course: &Course, cascade: bool) -> Result<(), ApiError> {
+    // dropped enrollments never block, they are removed along with the course
+    let students = students_courses_db_functions::get_enrollments_for_course(conn, course, None)?
+        .into_iter()
+        .filter(|(enrollment, _)| enrollment.status != EnrollmentStatus::Dropped)
+        .map(|(_, student)| student.email)
+        .collect::<Vec<String>>();
+    if !students.is_empty() && !cascade {
+        return Err(ApiError::BlockingEnrollments(students));
+    }
+    students_courses_db_functions::delete_student_courses_for_course(conn, course.id)?;
+    students_courses_db_functions::delete_waitlist_entries_for_course(conn, course.id)?;
+    course_prerequisites_db_functions::delete_prerequisites_for_course(conn, course.id)?;
+    course_meetings_db_functions::delete_meetings_for_course(conn, course.id)?;
+    course_offerings_db_functions::delete_offerings_for_course(conn, course.id)?;
+    instructors_db_functions::delete_assignments_for_course(conn, course.id)?;
+    db_functions::delete_course(conn, &course.name)?;
+    Ok(())
+}
+
+#[get("/{id}/prerequisites")]
+async fn get_course_prerequisites(data: web::Data<db::DbPool>, _principal: Principal, path: web::Path<i32>) -> Result<impl Responder, ApiError> {
+    let prerequisites = db::run(&data, move |conn| {
+        let course = db_functions::get_course_by_id(conn, path.into_inner())?;
+        Ok(course_prerequisites_db_functions::get_prerequisites(conn, course.id)?)
+    }).await?;
+    Ok(HttpResponse::Ok().json(prerequisites.into_iter().map(CourseResponse::from).collect::<Vec<CourseResponse>>()))
+}
+
+#[post("/{id}/prerequisites")]
+async fn add_course_prerequisite(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>, req: web::Json<AddPrerequisiteRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let prerequisite = db::run(&data, move |conn| {
+        let course = db_functions::get_course_by_id(conn, path.into_inner())?;
+        let prerequisite = db_functions::get_course_by_id(conn, req.prerequisite_id)?;
+        match course_prerequisites_db_functions::add_course_prerequisite(conn, course.id, prerequisite.id)? {
+            Some(_) => Ok(prerequisite),
+            None => Err(ApiError::PrerequisiteCycle),
+        }
+    }).await?;
+    Ok(CourseResponse::from(prerequisite))
+}
+
+#[delete("/{id}/prerequisites/{prerequisite_id}")]
+async fn delete_course_prerequisite(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<(i32, i32)>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let (course_id, prerequisite_id) = path.into_inner();
+    if db::run(&data, move |conn| Ok(course_prerequisites_db_functions::delete_course_prerequisite(conn, course_id, prerequisite_id)?)).await? == 0 {
+        return Err(ApiError::DBError(diesel::result::Error::NotFound));
+    }
+    Ok(HttpResponse::Ok().body("prerequisite deleted successfully"))
+}
+
+#[post("/{id}/instructors")]
+async fn assign_course_instructor(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>, req: web::Json<AssignInstructorRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let instructor = db::run(&data, move |conn| {
+        let course = db_functions::get_course_by_id(conn, path.into_inner())?;
+        let instructor = instructors_db_functions::get_instructor_by_id(conn, req.instructor_id)?;
+        instructors_db_functions::assign_instructor_to_course(conn, course.id, instructor.id)?;
+        Ok(instructor)
+    }).await?;
+    Ok(InstructorResponse::from(instructor))
+}
+
+#[delete("/{id}/instructors/{instructor_id}")]
+async fn unassign_course_instructor(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<(i32, i32)>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let (course_id, instructor_id) = path.into_inner();
+    if db::run(&data, move |conn| Ok(instructors_db_functions::unassign_instructor_from_course(conn, course_id, instructor_id)?)).await? == 0 {
+        return Err(ApiError::DBError(diesel::result::Error::NotFound));
+    }
+    Ok(HttpResponse::Ok().body("instructor unassigned successfully"))
+}
+
+#[get("/{id}/offerings/{offering_id}/meetings")]
+async fn get_course_meetings(data: web::Data<db::DbPool>, _principal: Principal, path: web::Path<(i32, i32)>) -> Result<impl Responder, ApiError> {
+    let (course_id, offering_id) = path.into_inner();
+    let meetings = db::run(&data, move |conn| {
+        let offering = course_offerings_db_functions::get_offering_for_course(conn, course_id, offering_id)?;
+        Ok(course_meetings_db_functions::get_meetings_for_offering(conn, offering.id)?)
+    }).await?;
+    Ok(HttpResponse::Ok().json(meetings.into_iter().map(CourseMeetingResponse::from).collect::<Vec<CourseMeetingResponse>>()))
+}
+
+#[post("/{id}/offerings/{offering_id}/meetings")]
+async fn create_course_meeting(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<(i32, i32)>, req: web::Json<CreateCourseMeetingRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    if req.end_time <= req.start_time { return Err(ApiError::bad_field("end_time", "must be after start_time")); }
+    let (course_id, offering_id) = path.into_inner();
+    let meeting = db::run(&data, move |conn| {
+        let offering = course_offerings_db_functions::get_offering_for_course(conn, course_id, offering_id)?;
+        let new_meeting = NewCourseMeeting { course_id: offering.course_id, offering_id: offering.id, day_of_week: req.day_of_week, start_time: req.start_time, end_time: req.end_time, room: req.room.clone() };
+        Ok(course_meetings_db_functions::create_course_meeting(conn, new_meeting)?)
+    }).await?;
+    Ok(CourseMeetingResponse::from(meeting))
+}
+
+#[delete("/{id}/offerings/{offering_id}/meetings/{meeting_id}")]
+async fn delete_course_meeting(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<(i32, i32, i32)>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let (course_id, offering_id, meeting_id) = path.into_inner();
+    let deleted = db::run(&data, move |conn| {
+        let offering = course_offerings_db_functions::get_offering_for_course(conn, course_id, offering_id)?;
+        Ok(course_meetings_db_functions::delete_course_meeting(conn, offering.id, meeting_id)?)
+    }).await?;
+    if deleted == 0 {
+        return Err(ApiError::DBError(diesel::result::Error::NotFound));
+    }
+    Ok(HttpResponse::Ok().body("meeting deleted successfully"))
+}
+
 #[cfg(test)]
 pub mod tests {
     use actix_web::{App, test, web};
+    use actix_web::http::StatusCode;
     use diesel::PgConnection;
 
+    use crate::api::auth::tests as auth_tests;
     use crate::api::courses::handlers::courses_api_scope;
-    use crate::api::courses::types::CreateCourseRequest;
+    use crate::api::courses::types::{AddPrerequisiteRequest, CreateCourseRequest};
+    use crate::api::imports::tests as imports_tests;
+    use crate::api::students::handlers::tests as students_tests;
+    use crate::api::students::types::CreateStudentRequest;
+    use crate::db::course_offerings::db_functions as course_offerings_db_functions;
     use crate::db::courses::db_functions;
     use crate::db::courses::models::NewCourse;
+    use crate::db::course_meetings::db_functions as course_meetings_db_functions;
     use crate::db::initialize_db_pool;
+    use crate::db::instructors::db_functions as instructors_db_functions;
+    use crate::db::students::db_functions as students_db_functions;
+    use crate::db::students_courses::db_functions as students_courses_db_functions;
+    use crate::db::students_courses::models::EnrollmentStatus;
 
     #[actix_web::test]
     async fn test_create_course_happy_path() {
@@ -47,6 +310,7 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(courses_api_scope())
         ).await;
 
@@ -69,6 +333,7 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(courses_api_scope())
         ).await;
 
@@ -93,6 +358,7 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(courses_api_scope())
         ).await;
 
@@ -114,6 +380,7 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(courses_api_scope())
         ).await;
 
@@ -126,14 +393,302 @@ pub mod tests {
         cleanup(&mut pool.clone().get().unwrap(), &request.name);
     }
 
+    #[actix_web::test]
+    async fn test_delete_course_happy_path() {
+        let pool = initialize_db_pool();
+        let request = CreateCourseRequest { name: String::from("pottery"), ..Default::default() };
+        setup_existing_course(true, &mut pool.clone().get().unwrap(), request.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/courses?name={}", &request.name))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert!(db_functions::get_course(&mut pool.clone().get().unwrap(), &request.name).is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_delete_course_with_enrollments() {
+        let pool = initialize_db_pool();
+        let request = CreateCourseRequest { name: String::from("sculpture"), ..Default::default() };
+        setup_existing_course(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let student = CreateStudentRequest { email: String::from("sculpture_student@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), student.clone().into());
+        let student_id = students_db_functions::get_student(&mut pool.clone().get().unwrap(), &student.email).unwrap().id;
+        let course_id = db_functions::get_course(&mut pool.clone().get().unwrap(), &request.name).unwrap().id;
+        students_courses_db_functions::tests::sign_up_for_course(&mut pool.clone().get().unwrap(), student_id, course_id).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/courses?name={}", &request.name))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/courses?name={}&cascade=true", &request.name))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert!(db_functions::get_course(&mut pool.clone().get().unwrap(), &request.name).is_err());
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &student.email);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_course_with_dropped_enrollment() {
+        let pool = initialize_db_pool();
+        let request = CreateCourseRequest { name: String::from("glassblowing"), ..Default::default() };
+        setup_existing_course(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let student = CreateStudentRequest { email: String::from("glassblowing_student@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), student.clone().into());
+        let student_id = students_db_functions::get_student(&mut pool.clone().get().unwrap(), &student.email).unwrap().id;
+        let course_id = db_functions::get_course(&mut pool.clone().get().unwrap(), &request.name).unwrap().id;
+        let offering_id = course_offerings_db_functions::tests::open_offering(&mut pool.clone().get().unwrap(), course_id).unwrap().id;
+        students_courses_db_functions::tests::sign_up_for_course(&mut pool.clone().get().unwrap(), student_id, course_id).unwrap();
+        students_courses_db_functions::update_student_course_status(&mut pool.clone().get().unwrap(), student_id, offering_id, EnrollmentStatus::Dropped).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/courses/{}", course_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert!(db_functions::get_course_by_id(&mut pool.clone().get().unwrap(), course_id).is_err());
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &student.email);
+    }
+
+    #[actix_web::test]
+    async fn test_course_routes_by_id() {
+        let pool = initialize_db_pool();
+        let request = CreateCourseRequest { name: String::from("printmaking"), ..Default::default() };
+        setup_existing_course(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let student = CreateStudentRequest { email: String::from("printmaking_student@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), student.clone().into());
+        let student_id = students_db_functions::get_student(&mut pool.clone().get().unwrap(), &student.email).unwrap().id;
+        let course_id = db_functions::get_course(&mut pool.clone().get().unwrap(), &request.name).unwrap().id;
+        students_courses_db_functions::tests::sign_up_for_course(&mut pool.clone().get().unwrap(), student_id, course_id).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/courses/{}", course_id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["name"], request.name.as_str());
+        assert_eq!(resp["instructors"], serde_json::json!([]));
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/courses/{}/students?fields=email", course_id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, serde_json::json!([student.email]));
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/courses/{}", course_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/courses/{}?cascade=true", course_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert!(db_functions::get_course_by_id(&mut pool.clone().get().unwrap(), course_id).is_err());
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &student.email);
+    }
+
+    #[actix_web::test]
+    async fn test_list_courses_happy_path() {
+        let pool = initialize_db_pool();
+        let request = CreateCourseRequest { name: String::from("ceramics"), ..Default::default() };
+        setup_existing_course(true, &mut pool.clone().get().unwrap(), request.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get()
+            .uri("/courses/list?limit=1&cursor=0")
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["items"].as_array().unwrap().len(), 1);
+        assert!(resp["next_cursor"].is_i64());
+        assert!(resp["total"].as_i64().unwrap() >= 1);
+
+        cleanup(&mut pool.clone().get().unwrap(), &request.name);
+    }
+
+    #[actix_web::test]
+    async fn test_list_courses_invalid_params() {
+        let pool = initialize_db_pool();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get()
+            .uri("/courses/list?limit=0")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::get()
+            .uri("/courses/list?sort=name&cursor=1")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::get()
+            .uri("/courses/list?offset=10&cursor=1")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_course_prerequisites() {
+        let pool = initialize_db_pool();
+        let request = CreateCourseRequest { name: String::from("organic chemistry"), ..Default::default() };
+        setup_existing_course(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let prerequisite = CreateCourseRequest { name: String::from("general chemistry"), ..Default::default() };
+        setup_existing_course(true, &mut pool.clone().get().unwrap(), prerequisite.clone().into());
+        let course_id = db_functions::get_course(&mut pool.clone().get().unwrap(), &request.name).unwrap().id;
+        let prerequisite_id = db_functions::get_course(&mut pool.clone().get().unwrap(), &prerequisite.name).unwrap().id;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/courses/{}/prerequisites", course_id))
+            .set_json(AddPrerequisiteRequest { prerequisite_id })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/courses/{}/prerequisites", prerequisite_id))
+            .set_json(AddPrerequisiteRequest { prerequisite_id: course_id })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/courses/{}/prerequisites", course_id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp[0]["name"], prerequisite.name.as_str());
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/courses/{}/prerequisites/{}", course_id, prerequisite_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/courses/{}/prerequisites/{}", course_id, prerequisite_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        cleanup(&mut pool.clone().get().unwrap(), &request.name);
+        cleanup(&mut pool.clone().get().unwrap(), &prerequisite.name);
+    }
+
+    #[actix_web::test]
+    async fn test_import_courses() {
+        let pool = initialize_db_pool();
+        let names = ["imported geology", "imported botany"];
+        for name in names {
+            setup_existing_course(false, &mut pool.clone().get().unwrap(), NewCourse { name: String::from(name), ..Default::default() });
+        }
+        let csv = format!("name,course_desc,capacity,credits\n{},rocks,30,3\n{},,,\nbroken,,-1,many\n", names[0], names[1]);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(courses_api_scope())
+        ).await;
+
+        let (content_type, body) = imports_tests::multipart_csv(&csv);
+        let req = test::TestRequest::post()
+            .uri("/courses/import?mode=skip_invalid")
+            .insert_header(("content-type", content_type))
+            .set_payload(body)
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["created"], 2);
+        assert_eq!(resp["skipped"], 1);
+        assert_eq!(resp["rows"][2]["errors"][0]["field"], "row");
+        let course = db_functions::get_course(&mut pool.clone().get().unwrap(), names[0]).unwrap();
+        assert_eq!((course.capacity, course.credits), (Some(30), 3));
+
+        let req = test::TestRequest::post()
+            .uri("/courses/import")
+            .insert_header(("content-type", "multipart/form-data; boundary=empty"))
+            .set_payload("--empty--\r\n")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        for name in names {
+            cleanup(&mut pool.clone().get().unwrap(), name);
+        }
+    }
+
     pub fn setup_existing_course(should_exist: bool, conn: &mut PgConnection, course: NewCourse) {
         match should_exist {
-            true => db_functions::create_course(conn, course).map(|_| ()).expect("setup failed"),
+            true => db_functions::create_course(conn, course).and_then(|x| course_offerings_db_functions::tests::open_offering(conn, x.id)).map(|_| ()).expect("setup failed"),
             false => db_functions::delete_course(conn, &course.name).map(|_| ()).expect("setup failed"),
         };
     }
 
     pub fn cleanup(conn: &mut PgConnection, email: &str) {
+        if let Ok(course) = db_functions::get_course(conn, email) {
+            students_courses_db_functions::delete_student_courses_for_course(conn, course.id).expect("cleanup failed");
+            students_courses_db_functions::delete_waitlist_entries_for_course(conn, course.id).expect("cleanup failed");
+            course_meetings_db_functions::delete_meetings_for_course(conn, course.id).expect("cleanup failed");
+            course_offerings_db_functions::delete_offerings_for_course(conn, course.id).expect("cleanup failed");
+            instructors_db_functions::delete_assignments_for_course(conn, course.id).expect("cleanup failed");
+        }
         db_functions::delete_course(conn, email).expect("cleanup failed");
     }
 }
\ No newline at end of file
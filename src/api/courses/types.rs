@@ -1,19 +1,73 @@
 use actix_web::{HttpRequest, HttpResponse, Responder};
 use actix_web::body::BoxBody;
 use actix_web::http::header::ContentType;
+use chrono::NaiveTime;
 use serde::{Deserialize, Serialize};
 
-use crate::db::courses::models::Course;
+use crate::api::instructors::types::InstructorResponse;
+use crate::api::students_courses::types::CourseStudentFields;
+use crate::db::course_meetings::models::{CourseMeeting, DayOfWeek};
+use crate::db::courses::models::{Course, CourseSortField};
+use crate::db::instructors::models::Instructor;
+use crate::db::students_courses::models::EnrollmentStatus;
 
 #[derive(Deserialize)]
 pub struct GetCourseRequest {
     pub name: String,
 }
 
+#[derive(Deserialize)]
+pub struct ListCoursesRequest {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    pub cursor: Option<i32>,
+    #[serde(default)]
+    pub sort: CourseSortField,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteCourseRequest {
+    pub name: String,
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteCourseByIdRequest {
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+#[derive(Deserialize)]
+pub struct GetCourseStudentsRequest {
+    pub fields: Option<CourseStudentFields>,
+    pub status: Option<EnrollmentStatus>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AddPrerequisiteRequest {
+    pub prerequisite_id: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AssignInstructorRequest {
+    pub instructor_id: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CreateCourseMeetingRequest {
+    pub day_of_week: DayOfWeek,
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+    pub room: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct CreateCourseRequest {
     pub name: String,
     pub course_desc: Option<String>,
+    pub capacity: Option<i32>,
+    pub credits: Option<i32>,
 }
 
 #[derive(Serialize, Debug)]
@@ -21,6 +75,8 @@ pub struct CourseResponse {
     id: i32,
     name: String,
     course_desc: Option<String>,
+    capacity: Option<i32>,
+    credits: i32,
 }
 
 impl From<Course> for CourseResponse {
@@ -29,6 +85,8 @@ impl From<Course> for CourseResponse {
             id: course.id,
             name: course.name,
             course_desc: course.course_desc,
+            capacity: course.capacity,
+            credits: course.credits,
         }
     }
 }
@@ -43,4 +101,69 @@ impl Responder for CourseResponse {
             .content_type(ContentType::json())
             .body(body)
     }
-}
\ No newline at end of file
+}
+
+#[derive(Serialize)]
+pub struct CourseDetailResponse {
+    #[serde(flatten)]
+    course: CourseResponse,
+    instructors: Vec<InstructorResponse>,
+}
+
+impl From<(Course, Vec<Instructor>)> for CourseDetailResponse {
+    fn from((course, instructors): (Course, Vec<Instructor>)) -> Self {
+        CourseDetailResponse {
+            course: CourseResponse::from(course),
+            instructors: instructors.into_iter().map(InstructorResponse::from).collect(),
+        }
+    }
+}
+
+impl Responder for CourseDetailResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = serde_json::to_string(&self).unwrap();
+
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct CourseMeetingResponse {
+    id: i32,
+    course_id: i32,
+    offering_id: i32,
+    day_of_week: DayOfWeek,
+    start_time: String,
+    end_time: String,
+    room: Option<String>,
+}
+
+impl From<CourseMeeting> for CourseMeetingResponse {
+    fn from(meeting: CourseMeeting) -> Self {
+        CourseMeetingResponse {
+            id: meeting.id,
+            course_id: meeting.course_id,
+            offering_id: meeting.offering_id,
+            day_of_week: meeting.day_of_week,
+            start_time: meeting.start_time.format("%H:%M").to_string(),
+            end_time: meeting.end_time.format("%H:%M").to_string(),
+            room: meeting.room,
+        }
+    }
+}
+
+impl Responder for CourseMeetingResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = serde_json::to_string(&self).unwrap();
+
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+}
@@ -3,21 +3,167 @@ use std::fmt::Debug;
 
 use actix_web::{error, HttpResponse};
 use actix_web::body::BoxBody;
-use actix_web::http::header::ContentType;
+use actix_web::http::header::{ContentType, RETRY_AFTER, WWW_AUTHENTICATE};
 use actix_web::http::StatusCode;
+use diesel::r2d2::PoolError;
+use serde::Serialize;
+use serde_json::{json, Value};
 use thiserror::Error;
 
+use crate::api::courses::types::CourseMeetingResponse;
+use crate::api::imports::ImportReport;
+use crate::api::request_id;
+use crate::api::students_courses::types::BatchReport;
+use crate::db::course_meetings::models::ScheduleConflict;
+use crate::db::students_courses::models::{EnrollmentStatus, SignUpRejection};
+
+// how long clients are asked to wait before retrying when no database connection is available
+pub const RETRY_AFTER_SECONDS: u32 = 5;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: &str) -> Self {
+        FieldError { field: field.to_string(), message: message.to_string() }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ErrorBody {
+    pub code: &'static str,
+    pub message: String,
+    pub details: Value,
+    pub request_id: Option<String>,
+}
+
 #[derive(Error)]
 pub enum ApiError {
     #[error("Internal Server Error occurred")]
     InternalError,
 
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("Bad Request")]
-    BadClientData,
+    BadClientData(Vec<FieldError>),
+
+    #[error("Blocked by existing enrollments: {0:?}")]
+    BlockingEnrollments(Vec<String>),
+
+    #[error("Missing prerequisites: {0:?}")]
+    MissingPrerequisites(Vec<String>),
+
+    #[error("Prerequisite would create a cycle")]
+    PrerequisiteCycle,
+
+    #[error("Schedule conflicts with courses: {}", .0.iter().map(|x| x.course.name.as_str()).collect::<Vec<&str>>().join(", "))]
+    ScheduleConflict(Vec<ScheduleConflict>),
+
+    #[error("Registration is closed for term {0}")]
+    RegistrationClosed(String),
+
+    #[error("Cannot change enrollment status from {} to {}", .0.as_str(), .1.as_str())]
+    InvalidStatusTransition(EnrollmentStatus, EnrollmentStatus),
+
+    #[error("Import rejected, {} of {} rows are invalid", .0.errored, .0.rows.len())]
+    ImportRejected(ImportReport),
+
+    #[error("Batch rejected, {} of {} items failed", .0.failed, .0.items.len())]
+    BatchRejected(BatchReport),
 
     #[error("DB Error: {0}")]
     DBError(#[from] diesel::result::Error),
 
+    #[error("Database unavailable: {0}")]
+    PoolUnavailable(#[from] PoolError),
+
+}
+
+impl ApiError {
+    pub fn bad_field(field: &str, message: &str) -> Self {
+        ApiError::BadClientData(vec![FieldError::new(field, message)])
+    }
+
+    fn code(&self) -> &'static str {
+        use diesel::result::*;
+        match self {
+            ApiError::InternalError => "internal_error",
+            ApiError::Unauthorized(_) => "unauthorized",
+            ApiError::Forbidden(_) => "forbidden",
+            ApiError::BadClientData(_) => "bad_request",
+            ApiError::BlockingEnrollments(_) => "blocking_enrollments",
+            ApiError::MissingPrerequisites(_) => "missing_prerequisites",
+            ApiError::PrerequisiteCycle => "prerequisite_cycle",
+            ApiError::ScheduleConflict(_) => "schedule_conflict",
+            ApiError::RegistrationClosed(_) => "registration_closed",
+            ApiError::ImportRejected(_) => "import_rejected",
+            ApiError::BatchRejected(_) => "batch_rejected",
+            ApiError::InvalidStatusTransition(_, _) => "invalid_status_transition",
+            ApiError::DBError(Error::NotFound) => "not_found",
+            ApiError::DBError(Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => "conflict",
+            ApiError::DBError(Error::DatabaseError(DatabaseErrorKind::SerializationFailure, _)) => "concurrent_update",
+            ApiError::DBError(_) => "database_error",
+            ApiError::PoolUnavailable(_) => "service_unavailable",
+        }
+    }
+
+    fn public_message(&self) -> String {
+        use diesel::result::*;
+        match self {
+            ApiError::DBError(Error::NotFound) => String::from("Record not found"),
+            ApiError::DBError(Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => String::from("Record already exists"),
+            ApiError::DBError(Error::DatabaseError(DatabaseErrorKind::SerializationFailure, _)) => String::from("Concurrent update, please retry"),
+            ApiError::DBError(_) => String::from("A database error occurred"),
+            ApiError::PoolUnavailable(_) => String::from("The database is temporarily unavailable, please retry"),
+            ApiError::BlockingEnrollments(_) => String::from("Blocked by existing enrollments"),
+            ApiError::MissingPrerequisites(_) => String::from("Missing prerequisites"),
+            ApiError::ScheduleConflict(_) => String::from("Meeting times overlap an enrolled course"),
+            _ => self.to_string(),
+        }
+    }
+
+    fn details(&self) -> Value {
+        match self {
+            ApiError::BadClientData(fields) => json!(fields),
+            ApiError::BlockingEnrollments(enrollments) => json!(enrollments),
+            ApiError::MissingPrerequisites(courses) => json!(courses),
+            ApiError::ScheduleConflict(conflicts) => json!(conflicts.iter().map(|conflict| json!({
+                "course_id": conflict.course.id,
+                "course_name": conflict.course.name,
+                "slot": CourseMeetingResponse::from(conflict.existing.clone()),
+                "conflicts_with": CourseMeetingResponse::from(conflict.requested.clone()),
+            })).collect::<Vec<Value>>()),
+            ApiError::InvalidStatusTransition(from, to) => json!({ "from": from, "to": to }),
+            ApiError::ImportRejected(report) => json!(report),
+            ApiError::BatchRejected(report) => json!(report),
+            _ => Value::Null,
+        }
+    }
+
+    pub fn to_body(&self) -> ErrorBody {
+        ErrorBody {
+            code: self.code(),
+            message: self.public_message(),
+            details: self.details(),
+            request_id: request_id::current(),
+        }
+    }
+}
+
+impl From<SignUpRejection> for ApiError {
+    fn from(rejection: SignUpRejection) -> Self {
+        match rejection {
+            SignUpRejection::MissingPrerequisites(courses) => ApiError::MissingPrerequisites(courses.into_iter().map(|x| x.name).collect()),
+            SignUpRejection::ScheduleConflict(conflicts) => ApiError::ScheduleConflict(conflicts),
+        }
+    }
 }
 
 impl error::ResponseError for ApiError {
@@ -25,17 +171,35 @@ impl error::ResponseError for ApiError {
         use diesel::result::*;
         match *self {
             ApiError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
-            ApiError::BadClientData => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::BadClientData(_) => StatusCode::BAD_REQUEST,
+            ApiError::BlockingEnrollments(_) => StatusCode::CONFLICT,
+            ApiError::MissingPrerequisites(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::PrerequisiteCycle => StatusCode::CONFLICT,
+            ApiError::ScheduleConflict(_) => StatusCode::CONFLICT,
+            ApiError::RegistrationClosed(_) => StatusCode::CONFLICT,
+            ApiError::ImportRejected(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::BatchRejected(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::InvalidStatusTransition(_, _) => StatusCode::CONFLICT,
             ApiError::DBError(Error::NotFound) => StatusCode::NOT_FOUND,
             ApiError::DBError(Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => StatusCode::CONFLICT,
+            ApiError::DBError(Error::DatabaseError(DatabaseErrorKind::SerializationFailure, _)) => StatusCode::CONFLICT,
             ApiError::DBError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::PoolUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 
     fn error_response(&self) -> HttpResponse<BoxBody> {
-        HttpResponse::build(self.status_code())
+        let mut response = HttpResponse::build(self.status_code());
+        match self {
+            ApiError::Unauthorized(_) => { response.insert_header((WWW_AUTHENTICATE, "Bearer")); }
+            ApiError::PoolUnavailable(_) => { response.insert_header((RETRY_AFTER, RETRY_AFTER_SECONDS)); }
+            _ => {}
+        }
+        response
             .content_type(ContentType::json())
-            .body(self.to_string())
+            .body(serde_json::to_string(&self.to_body()).unwrap())
     }
 }
 
@@ -47,4 +211,56 @@ impl Debug for ApiError {
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use actix_web::ResponseError;
+    use actix_web::http::header::RETRY_AFTER;
+    use actix_web::http::StatusCode;
+    use diesel::PgConnection;
+    use diesel::r2d2::{ConnectionManager, Pool};
+    use diesel::result::{DatabaseErrorKind, Error};
+
+    use crate::api::errors::{ApiError, FieldError};
+    use crate::api::request_id;
+    use crate::db;
+
+    #[test]
+    fn test_bad_client_data_body() {
+        let body = ApiError::bad_field("email", "invalid email format").to_body();
+        assert_eq!(body.code, "bad_request");
+        assert_eq!(body.details, serde_json::json!([FieldError::new("email", "invalid email format")]));
+        assert_eq!(body.request_id, None);
+    }
+
+    #[test]
+    fn test_db_error_body_is_sanitized() {
+        let error = ApiError::DBError(Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, Box::new(String::from("violates foreign key constraint"))));
+        let body = error.to_body();
+        assert_eq!(body.code, "database_error");
+        assert_eq!(body.message, "A database error occurred");
+        assert_eq!(error.status_code(), actix_web::http::StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[actix_web::test]
+    async fn test_error_body_carries_request_id() {
+        let body = request_id::scope(String::from("abc-123"), async { ApiError::DBError(Error::NotFound).to_body() }).await;
+        assert_eq!(body.code, "not_found");
+        assert_eq!(body.request_id, Some(String::from("abc-123")));
+    }
+
+    #[actix_web::test]
+    async fn test_unreachable_database_is_service_unavailable() {
+        let manager = ConnectionManager::<PgConnection>::new("postgres://postgres@127.0.0.1:1/unreachable");
+        let pool = Pool::builder().connection_timeout(Duration::from_millis(200)).build_unchecked(manager);
+        let error = db::run(&pool, |_| Ok(())).await.unwrap_err();
+        assert!(matches!(error, ApiError::PoolUnavailable(_)));
+        assert_eq!(error.to_body().code, "service_unavailable");
+        let response = error.error_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers().get(RETRY_AFTER).unwrap(), "5");
+    }
+}
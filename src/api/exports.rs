@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use actix_web::HttpResponse;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::web::Bytes;
+use diesel::{PgConnection, QueryResult};
+use futures_util::stream;
+use serde::{Deserialize, Serialize};
+
+use crate::api::errors::ApiError;
+use crate::db;
+use crate::db::DbPool;
+
+pub const EXPORT_BATCH_SIZE: i64 = 500;
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ExportRequest {
+    #[serde(default)]
+    pub format: ExportFormat,
+    pub student_id: Option<i32>,
+    pub course_id: Option<i32>,
+}
+
+struct ExportState<K, F> {
+    pool: DbPool,
+    fetch: Arc<F>,
+    format: ExportFormat,
+    columns: &'static [&'static str],
+    after: Option<K>,
+    first: bool,
+    done: bool,
+}
+
+// rows are read in keyset batches and written out as each batch arrives, so the table is never held in memory at once
+pub fn stream_export<K, R, F>(pool: DbPool, format: ExportFormat, name: &str, columns: &'static [&'static str], fetch: F) -> HttpResponse
+    where
+        K: Copy + Send + 'static,
+        R: Serialize + Send + 'static,
+        F: Fn(&mut PgConnection, Option<K>, i64) -> QueryResult<Vec<(K, R)>> + Send + Sync + 'static,
+{
+    let state = ExportState { pool, fetch: Arc::new(fetch), format, columns, after: None, first: true, done: false };
+    let body = stream::unfold(state, |mut state| async move {
+        if state.done { return None; }
+        let (fetch, after) = (state.fetch.clone(), state.after);
+        let rows = db::run(&state.pool, move |conn| Ok(fetch(conn, after, EXPORT_BATCH_SIZE)?)).await;
+        let rows = match rows {
+            Ok(rows) => rows,
+            Err(error) => {
+                state.done = true;
+                return Some((Err(error), state));
+            }
+        };
+        // the csv header normally comes from the first row, an empty export still gets one so it reads as a file without rows
+        if rows.is_empty() {
+            if !state.first || state.format != ExportFormat::Csv { return None; }
+            state.done = true;
+            return Some((Ok(Bytes::from(format!("{}\n", state.columns.join(",")))), state));
+        }
+        state.done = (rows.len() as i64) < EXPORT_BATCH_SIZE;
+        state.after = rows.last().map(|(key, _)| *key);
+        let chunk = encode_batch(state.format, rows.iter().map(|(_, row)| row), state.first);
+        state.first = false;
+        Some((chunk.map(Bytes::from), state))
+    });
+    HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!("{}.{}", name, format.extension()))],
+        })
+        .streaming(body)
+}
+
+pub fn encode_batch<'a, R: Serialize + 'a>(format: ExportFormat, rows: impl Iterator<Item=&'a R>, with_header: bool) -> Result<Vec<u8>, ApiError> {
+    match format {
+        ExportFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new().has_headers(with_header).from_writer(vec![]);
+            for row in rows {
+                writer.serialize(row).map_err(|_| ApiError::InternalError)?;
+            }
+            writer.into_inner().map_err(|_| ApiError::InternalError)
+        }
+        ExportFormat::Ndjson => {
+            let mut bytes = vec![];
+            for row in rows {
+                serde_json::to_writer(&mut bytes, row).map_err(|_| ApiError::InternalError)?;
+                bytes.push(b'\n');
+            }
+            Ok(bytes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::body;
+    use serde::Serialize;
+
+    use crate::api::exports::{encode_batch, ExportFormat, stream_export};
+    use crate::db::initialize_db_pool;
+
+    #[derive(Serialize)]
+    struct Row {
+        id: i32,
+        name: String,
+        note: Option<String>,
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row { id: 1, name: String::from("algebra, linear"), note: None },
+            Row { id: 2, name: String::from("botany"), note: Some(String::from("lab")) },
+        ]
+    }
+
+    #[test]
+    fn test_encode_csv_batches() {
+        let first = encode_batch(ExportFormat::Csv, rows().iter(), true).unwrap();
+        assert_eq!("id,name,note\n1,\"algebra, linear\",\n2,botany,lab\n", String::from_utf8(first).unwrap());
+        let next = encode_batch(ExportFormat::Csv, rows().iter(), false).unwrap();
+        assert_eq!("1,\"algebra, linear\",\n2,botany,lab\n", String::from_utf8(next).unwrap());
+    }
+
+    #[test]
+    fn test_encode_ndjson_batch() {
+        let bytes = encode_batch(ExportFormat::Ndjson, rows().iter(), true).unwrap();
+        assert_eq!("{\"id\":1,\"name\":\"algebra, linear\",\"note\":null}\n{\"id\":2,\"name\":\"botany\",\"note\":\"lab\"}\n", String::from_utf8(bytes).unwrap());
+    }
+
+    #[actix_web::test]
+    async fn test_stream_empty_export() {
+        let columns = &["id", "name", "note"];
+        let resp = stream_export(initialize_db_pool(), ExportFormat::Csv, "rows", columns, |_, _: Option<i32>, _| Ok(Vec::<(i32, Row)>::new()));
+        let bytes = body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!("id,name,note\n", String::from_utf8(bytes.to_vec()).unwrap());
+
+        let resp = stream_export(initialize_db_pool(), ExportFormat::Ndjson, "rows", columns, |_, _: Option<i32>, _| Ok(Vec::<(i32, Row)>::new()));
+        let bytes = body::to_bytes(resp.into_body()).await.unwrap();
+        assert!(bytes.is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+use actix_web::{get, HttpResponse, Responder, web};
+use actix_web::http::StatusCode;
+use diesel_migrations::MigrationHarness;
+
+use crate::api::health::types::{CheckStatus, HealthResponse, ReadinessResponse};
+use crate::db;
+use crate::MIGRATIONS;
+
+// registered at the root rather than as a scope, so they live at /healthz and /readyz
+pub fn health_api_config(cfg: &mut web::ServiceConfig) {
+    cfg.service(liveness).service(readiness);
+}
+
+#[get("/healthz")]
+async fn liveness() -> impl Responder {
+    HttpResponse::Ok().json(HealthResponse { status: CheckStatus::Ok })
+}
+
+#[get("/readyz")]
+async fn readiness(data: web::Data<db::DbPool>) -> impl Responder {
+    let pending = db::run(&data, |conn| {
+        Ok(conn.pending_migrations(MIGRATIONS).ok().map(|migrations| migrations.iter().map(|x| x.name().to_string()).collect::<Vec<String>>()))
+    }).await;
+    let response = match pending {
+        Ok(pending) => ReadinessResponse::new(true, pending),
+        Err(_) => ReadinessResponse::new(false, None),
+    };
+    let status = if response.ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    HttpResponse::build(status).json(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use actix_web::{App, test, web};
+    use actix_web::http::StatusCode;
+    use diesel::PgConnection;
+    use diesel::r2d2::{ConnectionManager, Pool};
+
+    use crate::api::auth::{AuthConfig, Authentication};
+    use crate::api::health::handlers::health_api_config;
+    use crate::api::health::types::{CheckStatus, ReadinessResponse};
+    use crate::db::initialize_db_pool;
+
+    #[actix_web::test]
+    async fn test_probes_skip_authentication() {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(initialize_db_pool()))
+                .wrap(Authentication::new(Arc::new(AuthConfig::default())))
+                .configure(health_api_config)
+        ).await;
+
+        let req = test::TestRequest::get().uri("/healthz").to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["status"], "ok");
+
+        let req = test::TestRequest::get().uri("/readyz").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["ready"], true);
+        assert_eq!(body["database"], "ok");
+        assert_eq!(body["pending_migrations"], serde_json::json!([]));
+    }
+
+    #[actix_web::test]
+    async fn test_readiness_fails_without_database() {
+        let manager = ConnectionManager::<PgConnection>::new("postgres://postgres@127.0.0.1:1/unreachable");
+        let pool = Pool::builder().connection_timeout(Duration::from_millis(200)).build_unchecked(manager);
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .configure(health_api_config)
+        ).await;
+
+        let req = test::TestRequest::get().uri("/readyz").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["ready"], false);
+        assert_eq!(body["database"], "failed");
+        assert_eq!(body["migrations"], "skipped");
+    }
+
+    #[actix_web::test]
+    async fn test_pending_migrations_are_not_ready() {
+        let response = ReadinessResponse::new(true, Some(vec![String::from("2026-10-18-100000_create_audit_events")]));
+        assert!(!response.ready);
+        assert_eq!(response.migrations, CheckStatus::Pending);
+        assert!(!ReadinessResponse::new(true, None).ready);
+        assert!(ReadinessResponse::new(true, Some(vec![])).ready);
+    }
+}
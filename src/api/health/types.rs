@@ -0,0 +1,36 @@
+use serde::Serialize;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    Failed,
+    Pending,
+    Skipped,
+}
+
+#[derive(Serialize)]
+pub struct HealthResponse {
+    pub status: CheckStatus,
+}
+
+#[derive(Serialize)]
+pub struct ReadinessResponse {
+    pub ready: bool,
+    pub database: CheckStatus,
+    pub migrations: CheckStatus,
+    pub pending_migrations: Vec<String>,
+}
+
+impl ReadinessResponse {
+    // None means the connection was fine but the migrations table could not be read
+    pub fn new(database_up: bool, pending_migrations: Option<Vec<String>>) -> Self {
+        let (database, migrations, pending_migrations) = match (database_up, pending_migrations) {
+            (false, _) => (CheckStatus::Failed, CheckStatus::Skipped, vec![]),
+            (true, None) => (CheckStatus::Ok, CheckStatus::Failed, vec![]),
+            (true, Some(pending)) if pending.is_empty() => (CheckStatus::Ok, CheckStatus::Ok, pending),
+            (true, Some(pending)) => (CheckStatus::Ok, CheckStatus::Pending, pending),
+        };
+        ReadinessResponse { ready: database == CheckStatus::Ok && migrations == CheckStatus::Ok, database, migrations, pending_migrations }
+    }
+}
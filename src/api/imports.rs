@@ -0,0 +1,184 @@
+use actix_multipart::Multipart;
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use actix_web::body::BoxBody;
+use actix_web::http::header::ContentType;
+use diesel::{Connection, PgConnection, QueryResult};
+use diesel::result::{DatabaseErrorKind, Error};
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::api::errors::{ApiError, FieldError};
+
+pub const MAX_IMPORT_BYTES: usize = 5 * 1024 * 1024;
+
+// line number in the uploaded file and the parsed row, or why it could not be parsed
+pub type CsvRow<T> = (u64, Result<T, String>);
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    #[default]
+    Atomic,
+    SkipInvalid,
+}
+
+#[derive(Deserialize)]
+pub struct ImportRequest {
+    #[serde(default)]
+    pub mode: ImportMode,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportRowStatus {
+    Created,
+    Skipped,
+    Error,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ImportRowReport {
+    pub line: u64,
+    pub status: ImportRowStatus,
+    pub id: Option<i32>,
+    pub errors: Vec<FieldError>,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct ImportReport {
+    pub created: usize,
+    pub skipped: usize,
+    pub errored: usize,
+    pub rows: Vec<ImportRowReport>,
+}
+
+impl ImportReport {
+    fn push(&mut self, row: ImportRowReport) {
+        match row.status {
+            ImportRowStatus::Created => self.created += 1,
+            ImportRowStatus::Skipped => self.skipped += 1,
+            ImportRowStatus::Error => self.errored += 1,
+        }
+        self.rows.push(row);
+    }
+
+    // nothing was written, so rows that would have been created are reported as skipped
+    fn rolled_back(self) -> Self {
+        let mut report = ImportReport::default();
+        for row in self.rows {
+            match row.status {
+                ImportRowStatus::Created => report.push(ImportRowReport { status: ImportRowStatus::Skipped, id: None, ..row }),
+                _ => report.push(row),
+            }
+        }
+        report
+    }
+}
+
+impl Responder for ImportReport {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = serde_json::to_string(&self).unwrap();
+
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+}
+
+pub async fn read_csv_upload(mut payload: Multipart) -> Result<Vec<u8>, ApiError> {
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|_| ApiError::bad_field("file", "malformed multipart body"))?;
+        if field.name() != Some("file") { continue; }
+        let mut bytes = vec![];
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|_| ApiError::bad_field("file", "malformed multipart body"))?;
+            if bytes.len() + chunk.len() > MAX_IMPORT_BYTES { return Err(ApiError::bad_field("file", "file is too large")); }
+            bytes.extend_from_slice(&chunk);
+        }
+        return Ok(bytes);
+    }
+    Err(ApiError::bad_field("file", "missing csv file"))
+}
+
+pub fn parse_csv<T: DeserializeOwned>(bytes: &[u8]) -> Result<Vec<CsvRow<T>>, ApiError> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(bytes);
+    let headers = reader.headers().map_err(|_| ApiError::bad_field("file", "could not read csv header"))?.clone();
+    let mut rows = vec![];
+    for record in reader.records() {
+        match record {
+            Ok(record) => {
+                let line = record.position().map(|x| x.line()).unwrap_or_default();
+                rows.push((line, record.deserialize(Some(&headers)).map_err(|e| e.to_string())));
+            }
+            Err(error) => {
+                let line = error.position().map(|x| x.line()).unwrap_or_default();
+                rows.push((line, Err(error.to_string())));
+            }
+        }
+    }
+    Ok(rows)
+}
+
+pub fn run_import<T, V, I>(conn: &mut PgConnection, mode: ImportMode, rows: Vec<CsvRow<T>>, unique_field: &str, validate: V, mut insert: I) -> Result<ImportReport, ApiError>
+    where
+        V: Fn(&T) -> Vec<FieldError>,
+        I: FnMut(&mut PgConnection, T) -> QueryResult<i32>,
+{
+    conn.transaction::<_, ApiError, _>(|conn| {
+        let mut report = ImportReport::default();
+        for (line, row) in rows {
+            let row = row.map_err(|message| vec![FieldError::new("row", &message)])
+                .and_then(|row| {
+                    let errors = validate(&row);
+                    if errors.is_empty() { Ok(row) } else { Err(errors) }
+                });
+            let (status, id, errors) = match row {
+                // each row gets its own savepoint so a duplicate does not abort the whole import
+                Ok(row) => match conn.transaction(|conn| insert(conn, row)) {
+                    Ok(id) => (ImportRowStatus::Created, Some(id), vec![]),
+                    Err(Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => (ImportRowStatus::Error, None, vec![FieldError::new(unique_field, "already exists")]),
+                    Err(error) => return Err(ApiError::from(error)),
+                },
+                Err(errors) => (ImportRowStatus::Error, None, errors),
+            };
+            let status = match (mode, status) {
+                (ImportMode::SkipInvalid, ImportRowStatus::Error) => ImportRowStatus::Skipped,
+                (_, status) => status,
+            };
+            report.push(ImportRowReport { line, status, id, errors });
+        }
+        if report.errored > 0 {
+            return Err(ApiError::ImportRejected(report.rolled_back()));
+        }
+        Ok(report)
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::api::imports::parse_csv;
+    use crate::api::students::types::CreateStudentRequest;
+
+    #[test]
+    fn test_parse_csv_rows() {
+        let rows = parse_csv::<CreateStudentRequest>(b"email,phone_number\nfoo@bar.com,\n bar@baz.com , 91234567\nbaz@qux.com,1,extra\n").unwrap();
+        assert_eq!(3, rows.len());
+        let (line, first) = &rows[0];
+        assert_eq!(2, *line);
+        assert_eq!(None, first.as_ref().unwrap().phone_number);
+        assert_eq!(Some(String::from("91234567")), rows[1].1.as_ref().unwrap().phone_number);
+        assert_eq!("bar@baz.com", rows[1].1.as_ref().unwrap().email);
+        assert!(rows[2].1.is_err());
+    }
+
+    pub fn multipart_csv(csv: &str) -> (String, Vec<u8>) {
+        let boundary = "csv-import-boundary";
+        let body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"import.csv\"\r\nContent-Type: text/csv\r\n\r\n{csv}\r\n--{boundary}--\r\n"
+        );
+        (format!("multipart/form-data; boundary={}", boundary), body.into_bytes())
+    }
+}
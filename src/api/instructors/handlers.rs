@@ -0,0 +1,383 @@
+use actix_web::{delete, get, HttpResponse, patch, post, Responder, Scope, web};
+use diesel::Connection;
+
+use crate::api::auth::Principal;
+use crate::api::errors::ApiError;
+use crate::api::instructors::types::{CreateInstructorRequest, DeleteInstructorRequest, GetInstructorRequest, InstructorDetailResponse, InstructorResponse, ListInstructorsRequest, UpdateInstructorRequest};
+use crate::api::pagination;
+use crate::api::pagination::PageResponse;
+use crate::api::policy;
+use crate::api::utils;
+use crate::db;
+use crate::db::instructors::db_functions;
+use crate::db::instructors::models::{InstructorSortField, NewInstructor, UpdateInstructor};
+use crate::db::students_courses::db_functions as students_courses_db_functions;
+
+pub fn instructors_api_scope() -> Scope {
+    web::scope("/instructors")
+        .service(get_instructor)
+        .service(list_instructors)
+        .service(get_instructor_courses)
+        .service(create_instructor)
+        .service(update_instructor)
+        .service(delete_instructor)
+}
+
+#[get("")]
+async fn get_instructor(data: web::Data<db::DbPool>, _principal: Principal, params: web::Query<GetInstructorRequest>) -> Result<impl Responder, ApiError> {
+    let instructor = db::run(&data, move |conn| Ok(db_functions::get_instructor(conn, &params.email)?)).await?;
+    Ok(InstructorResponse::from(instructor))
+}
+
+#[get("/list")]
+async fn list_instructors(data: web::Data<db::DbPool>, _principal: Principal, params: web::Query<ListInstructorsRequest>) -> Result<impl Responder, ApiError> {
+    let (limit, offset) = pagination::page_bounds(params.limit, params.offset, params.cursor)?;
+    if params.cursor.is_some() && params.sort != InstructorSortField::Id { return Err(ApiError::bad_field("cursor", "cursor pagination requires sort=id")); }
+    let (sort, cursor) = (params.sort, params.cursor);
+    let (instructors, total) = db::run(&data, move |conn| {
+        let instructors = db_functions::list_instructors(conn, sort, limit, offset, cursor)?;
+        let total = db_functions::count_instructors(conn)?;
+        Ok((instructors, total))
+    }).await?;
+    let next_cursor = match sort {
+        InstructorSortField::Id => pagination::next_cursor(&instructors.iter().map(|x| x.id).collect::<Vec<i32>>(), limit),
+        _ => None,
+    };
+    Ok(PageResponse { items: instructors.into_iter().map(InstructorResponse::from).collect(), next_cursor, total })
+}
+
+#[get("/{id}")]
+async fn get_instructor_courses(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>) -> Result<impl Responder, ApiError> {
+    let detail = db::run(&data, move |conn| {
+        let instructor = db_functions::get_instructor_by_id(conn, path.into_inner())?;
+        let mut courses = vec![];
+        for course in db_functions::get_courses_for_instructor(conn, &instructor)? {
+            policy::require_roster_access(conn, &principal, &course)?;
+            let roster = students_courses_db_functions::get_enrollments_for_course(conn, &course, None)?;
+            courses.push((course, roster.into_iter().map(|(_, student)| student).collect()));
+        }
+        Ok((instructor, courses))
+    }).await?;
+    Ok(InstructorDetailResponse::from(detail))
+}
+
+#[post("")]
+async fn create_instructor(data: web::Data<db::DbPool>, principal: Principal, req: web::Json<CreateInstructorRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    if !utils::validate_email(req.email.as_str()) { return Err(ApiError::bad_field("email", "invalid email format")); }
+    if req.name.trim().is_empty() { return Err(ApiError::bad_field("name", "must not be empty")); }
+    let instructor = db::run(&data, move |conn| Ok(db_functions::create_instructor(conn, NewInstructor::from(req.0))?)).await?;
+    Ok(InstructorResponse::from(instructor))
+}
+
+#[patch("/{id}")]
+async fn update_instructor(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>, req: web::Json<UpdateInstructorRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    if let Some(new_email) = &req.email {
+        if !utils::validate_email(new_email.as_str()) { return Err(ApiError::bad_field("email", "invalid email format")); }
+    }
+    if req.name.as_ref().is_some_and(|name| name.trim().is_empty()) { return Err(ApiError::bad_field("name", "must not be empty")); }
+    let instructor = db::run(&data, move |conn| Ok(db_functions::update_instructor(conn, path.into_inner(), UpdateInstructor::from(req.0))?)).await?;
+    Ok(InstructorResponse::from(instructor))
+}
+
+#[delete("")]
+async fn delete_instructor(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<DeleteInstructorRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::ADMIN)?;
+    db::run(&data, move |conn| {
+        conn.transaction::<_, ApiError, _>(|conn| {
+            let instructor = db_functions::get_instructor(conn, &params.email)?;
+            db_functions::delete_assignments_for_instructor(conn, instructor.id)?;
+            db_functions::delete_instructor(conn, &instructor.email)?;
+            Ok(())
+        })
+    }).await?;
+    Ok(HttpResponse::Ok().body("instructor deleted successfully"))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use actix_web::{App, test, web};
+    use actix_web::http::StatusCode;
+    use diesel::PgConnection;
+
+    use crate::api::auth::tests as auth_tests;
+    use crate::api::courses::handlers::courses_api_scope;
+    use crate::api::courses::handlers::tests as courses_tests;
+    use crate::api::courses::types::{AssignInstructorRequest, CreateCourseRequest};
+    use crate::api::instructors::handlers::instructors_api_scope;
+    use crate::api::instructors::types::{CreateInstructorRequest, UpdateInstructorRequest};
+    use crate::api::students::handlers::tests as students_tests;
+    use crate::api::students::types::CreateStudentRequest;
+    use crate::db::api_keys::models::Role;
+    use crate::db::courses::db_functions as courses_db_functions;
+    use crate::db::initialize_db_pool;
+    use crate::db::instructors::db_functions;
+    use crate::db::instructors::models::NewInstructor;
+    use crate::db::students::db_functions as students_db_functions;
+    use crate::db::students_courses::db_functions as students_courses_db_functions;
+
+    #[actix_web::test]
+    async fn test_create_instructor_happy_path() {
+        let pool = initialize_db_pool();
+        let request = CreateInstructorRequest { name: String::from("Jane Doe"), email: String::from("sample_instructor_one@gmail.com") };
+        setup_existing_instructor(false, &mut pool.clone().get().unwrap(), request.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(instructors_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/instructors")
+            .set_json(request.clone())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::post()
+            .uri("/instructors")
+            .set_json(request.clone())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/instructors?email={}", &request.email))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["name"], "Jane Doe");
+
+        cleanup(&mut pool.clone().get().unwrap(), &request.email);
+    }
+
+    #[actix_web::test]
+    async fn test_create_instructor_invalid_email_format() {
+        let pool = initialize_db_pool();
+        let request = CreateInstructorRequest { name: String::from("Jane Doe"), email: String::from("invalid email") };
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(instructors_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/instructors")
+            .set_json(request.clone())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_update_and_delete_instructor() {
+        let pool = initialize_db_pool();
+        let request = CreateInstructorRequest { name: String::from("Jane Doe"), email: String::from("sample_instructor_two@gmail.com") };
+        setup_existing_instructor(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let instructor = db_functions::get_instructor(&mut pool.clone().get().unwrap(), &request.email).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(instructors_api_scope())
+        ).await;
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("/instructors/{}", instructor.id))
+            .set_json(UpdateInstructorRequest { name: Some(String::from("Jane Smith")), ..Default::default() })
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["name"], "Jane Smith");
+        assert_eq!(resp["email"], request.email.as_str());
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/instructors?email={}", &request.email))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/instructors/{}", instructor.id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn test_assign_instructor_to_course() {
+        let pool = initialize_db_pool();
+        let request = CreateInstructorRequest { name: String::from("Jane Doe"), email: String::from("sample_instructor_three@gmail.com") };
+        setup_existing_instructor(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let instructor = db_functions::get_instructor(&mut pool.clone().get().unwrap(), &request.email).unwrap();
+        let course = CreateCourseRequest { name: String::from("woodworking"), ..Default::default() };
+        courses_tests::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let course_id = courses_db_functions::get_course(&mut pool.clone().get().unwrap(), &course.name).unwrap().id;
+        let student = CreateStudentRequest { email: String::from("woodworking_student@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), student.clone().into());
+        let student_id = students_db_functions::get_student(&mut pool.clone().get().unwrap(), &student.email).unwrap().id;
+        students_courses_db_functions::tests::sign_up_for_course(&mut pool.clone().get().unwrap(), student_id, course_id).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(instructors_api_scope())
+                .service(courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/courses/{}/instructors", course_id))
+            .set_json(AssignInstructorRequest { instructor_id: instructor.id })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/courses?name={}", &course.name))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["instructors"][0]["email"], request.email.as_str());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/instructors/{}", instructor.id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["courses"][0]["name"], course.name.as_str());
+        assert_eq!(resp["courses"][0]["roster"][0]["email"], student.email.as_str());
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/courses/{}/instructors/{}", course_id, instructor.id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/courses/{}/instructors/{}", course_id, instructor.id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &student.email);
+        courses_tests::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+        cleanup(&mut pool.clone().get().unwrap(), &request.email);
+    }
+
+    #[actix_web::test]
+    async fn test_instructor_writes_require_staff() {
+        let pool = initialize_db_pool();
+        let request = CreateInstructorRequest { name: String::from("Jane Doe"), email: String::from("sample_instructor_four@gmail.com") };
+        setup_existing_instructor(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let instructor = db_functions::get_instructor(&mut pool.clone().get().unwrap(), &request.email).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(|req, srv| auth_tests::as_principal(req, srv, "sample_instructor_four@gmail.com", Role::Instructor))
+                .service(instructors_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/instructors")
+            .set_json(CreateInstructorRequest { name: String::from("John Doe"), email: String::from("sample_instructor_five@gmail.com") })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("/instructors/{}", instructor.id))
+            .set_json(UpdateInstructorRequest { name: Some(String::from("Jane Smith")), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/instructors?email={}", &request.email))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/instructors?email={}", &request.email))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["name"], "Jane Doe");
+
+        cleanup(&mut pool.clone().get().unwrap(), &request.email);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_instructor_requires_admin() {
+        let pool = initialize_db_pool();
+        let request = CreateInstructorRequest { name: String::from("Jane Doe"), email: String::from("sample_instructor_eight@gmail.com") };
+        setup_existing_instructor(true, &mut pool.clone().get().unwrap(), request.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(|req, srv| auth_tests::as_principal(req, srv, "registrar@example.com", Role::Registrar))
+                .service(instructors_api_scope())
+        ).await;
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/instructors?email={}", &request.email))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        assert!(db_functions::get_instructor(&mut pool.clone().get().unwrap(), &request.email).is_ok());
+
+        cleanup(&mut pool.clone().get().unwrap(), &request.email);
+    }
+
+    #[actix_web::test]
+    async fn test_instructor_roster_requires_assignment() {
+        let pool = initialize_db_pool();
+        let request = CreateInstructorRequest { name: String::from("Jane Doe"), email: String::from("sample_instructor_six@gmail.com") };
+        setup_existing_instructor(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let instructor = db_functions::get_instructor(&mut pool.clone().get().unwrap(), &request.email).unwrap();
+        let course = CreateCourseRequest { name: String::from("bookbinding"), ..Default::default() };
+        courses_tests::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let course_id = courses_db_functions::get_course(&mut pool.clone().get().unwrap(), &course.name).unwrap().id;
+        db_functions::assign_instructor_to_course(&mut pool.clone().get().unwrap(), course_id, instructor.id).unwrap();
+
+        for (email, role, status) in [
+            ("sample_instructor_six@gmail.com", Role::Instructor, StatusCode::OK),
+            ("sample_instructor_seven@gmail.com", Role::Instructor, StatusCode::FORBIDDEN),
+            ("bookbinding_student@gmail.com", Role::Student, StatusCode::FORBIDDEN),
+        ] {
+            let app = test::init_service(
+                App::new()
+                    .app_data(web::Data::new(pool.clone()))
+                    .wrap_fn(move |req, srv| auth_tests::as_principal(req, srv, email, role))
+                    .service(instructors_api_scope())
+            ).await;
+
+            let req = test::TestRequest::get()
+                .uri(&format!("/instructors/{}", instructor.id))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status, "{}", email);
+        }
+
+        courses_tests::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+        cleanup(&mut pool.clone().get().unwrap(), &request.email);
+    }
+
+    pub fn setup_existing_instructor(should_exist: bool, conn: &mut PgConnection, instructor: NewInstructor) {
+        match should_exist {
+            true => db_functions::create_instructor(conn, instructor).map(|_| ()).expect("setup failed"),
+            false => db_functions::delete_instructor(conn, &instructor.email).map(|_| ()).expect("setup failed"),
+        };
+    }
+
+    pub fn cleanup(conn: &mut PgConnection, email: &str) {
+        if let Ok(instructor) = db_functions::get_instructor(conn, email) {
+            db_functions::delete_assignments_for_instructor(conn, instructor.id).expect("cleanup failed");
+        }
+        db_functions::delete_instructor(conn, email).expect("cleanup failed");
+    }
+}
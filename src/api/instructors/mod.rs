@@ -0,0 +1,2 @@
+pub mod handlers;
+pub mod types;
\ No newline at end of file
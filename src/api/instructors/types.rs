@@ -0,0 +1,119 @@
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use actix_web::body::BoxBody;
+use actix_web::http::header::ContentType;
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::api::courses::types::CourseResponse;
+use crate::api::students::types::StudentResponse;
+use crate::db::courses::models::Course;
+use crate::db::instructors::models::{Instructor, InstructorSortField};
+use crate::db::students::models::Student;
+
+#[derive(Deserialize)]
+pub struct GetInstructorRequest {
+    pub email: String,
+}
+
+#[derive(Deserialize)]
+pub struct ListInstructorsRequest {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    pub cursor: Option<i32>,
+    #[serde(default)]
+    pub sort: InstructorSortField,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteInstructorRequest {
+    pub email: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct CreateInstructorRequest {
+    pub name: String,
+    pub email: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct UpdateInstructorRequest {
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct InstructorResponse {
+    id: i32,
+    name: String,
+    email: String,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+}
+
+impl From<Instructor> for InstructorResponse {
+    fn from(instructor: Instructor) -> Self {
+        InstructorResponse {
+            id: instructor.id,
+            name: instructor.name,
+            email: instructor.email,
+            created_at: instructor.created_at.map(|x| Local.from_utc_datetime(&x).format("%Y-%m-%d %H:%M:%S").to_string()),
+            updated_at: instructor.updated_at.map(|x| Local.from_utc_datetime(&x).format("%Y-%m-%d %H:%M:%S").to_string()),
+        }
+    }
+}
+
+impl Responder for InstructorResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = serde_json::to_string(&self).unwrap();
+
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+}
+
+#[derive(Serialize)]
+pub struct TaughtCourseResponse {
+    #[serde(flatten)]
+    course: CourseResponse,
+    roster: Vec<StudentResponse>,
+}
+
+impl From<(Course, Vec<Student>)> for TaughtCourseResponse {
+    fn from((course, roster): (Course, Vec<Student>)) -> Self {
+        TaughtCourseResponse {
+            course: CourseResponse::from(course),
+            roster: roster.into_iter().map(StudentResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct InstructorDetailResponse {
+    #[serde(flatten)]
+    instructor: InstructorResponse,
+    courses: Vec<TaughtCourseResponse>,
+}
+
+impl From<(Instructor, Vec<(Course, Vec<Student>)>)> for InstructorDetailResponse {
+    fn from((instructor, courses): (Instructor, Vec<(Course, Vec<Student>)>)) -> Self {
+        InstructorDetailResponse {
+            instructor: InstructorResponse::from(instructor),
+            courses: courses.into_iter().map(TaughtCourseResponse::from).collect(),
+        }
+    }
+}
+
+impl Responder for InstructorDetailResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = serde_json::to_string(&self).unwrap();
+
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+}
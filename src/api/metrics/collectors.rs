@@ -0,0 +1,108 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use diesel::r2d2::event::{CheckoutEvent, HandleEvent, TimeoutEvent};
+use diesel::r2d2::State;
+use prometheus::core::Collector;
+use prometheus::{Encoder, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder};
+
+const POOL_WAIT_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    pool_connections: IntGauge,
+    pool_idle_connections: IntGauge,
+    pool_max_connections: IntGauge,
+    pool_wait: Histogram,
+    pool_timeouts: IntCounter,
+    students: IntGauge,
+    courses: IntGauge,
+    course_enrollments: IntGaugeVec,
+    course_waitlist: IntGaugeVec,
+}
+
+pub struct DomainCounts {
+    pub students: i64,
+    pub courses: i64,
+    pub enrolled_by_course: Vec<(String, i64)>,
+    pub waitlisted_by_course: Vec<(String, i64)>,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+        Metrics {
+            http_requests: register(&registry, IntCounterVec::new(Opts::new("http_requests_total", "HTTP requests handled, by route and status"), &["method", "route", "status"]).unwrap()),
+            http_request_duration: register(&registry, HistogramVec::new(HistogramOpts::new("http_request_duration_seconds", "Time taken to produce a response, by route"), &["method", "route"]).unwrap()),
+            pool_connections: register(&registry, IntGauge::new("db_pool_connections", "Connections currently open in the pool").unwrap()),
+            pool_idle_connections: register(&registry, IntGauge::new("db_pool_idle_connections", "Open connections not checked out").unwrap()),
+            pool_max_connections: register(&registry, IntGauge::new("db_pool_max_connections", "Maximum number of connections the pool will open").unwrap()),
+            pool_wait: register(&registry, Histogram::with_opts(HistogramOpts::new("db_pool_wait_seconds", "Time spent waiting to check out a connection").buckets(POOL_WAIT_BUCKETS.to_vec())).unwrap()),
+            pool_timeouts: register(&registry, IntCounter::new("db_pool_timeouts_total", "Checkouts that gave up waiting for a connection").unwrap()),
+            students: register(&registry, IntGauge::new("students", "Registered students").unwrap()),
+            courses: register(&registry, IntGauge::new("courses", "Courses in the catalog").unwrap()),
+            course_enrollments: register(&registry, IntGaugeVec::new(Opts::new("course_enrollments", "Students currently enrolled, by course"), &["course"]).unwrap()),
+            course_waitlist: register(&registry, IntGaugeVec::new(Opts::new("course_waitlist", "Students on the waitlist, by course"), &["course"]).unwrap()),
+            registry,
+        }
+    }
+
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        self.http_requests.with_label_values(&[method, route, &status.to_string()]).inc();
+        self.http_request_duration.with_label_values(&[method, route]).observe(elapsed.as_secs_f64());
+    }
+
+    pub fn set_pool_state(&self, state: State, max_size: u32) {
+        self.pool_connections.set(state.connections as i64);
+        self.pool_idle_connections.set(state.idle_connections as i64);
+        self.pool_max_connections.set(max_size as i64);
+    }
+
+    pub fn set_domain_counts(&self, counts: DomainCounts) {
+        self.students.set(counts.students);
+        self.courses.set(counts.courses);
+        set_by_course(&self.course_enrollments, counts.enrolled_by_course);
+        set_by_course(&self.course_waitlist, counts.waitlisted_by_course);
+    }
+
+    pub fn encode(&self) -> String {
+        let mut buffer = vec![];
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer).expect("metrics should encode as text");
+        String::from_utf8(buffer).expect("text format should be valid UTF-8")
+    }
+}
+
+fn register<T: Collector + Clone + 'static>(registry: &Registry, collector: T) -> T {
+    registry.register(Box::new(collector.clone())).expect("metric names should be unique");
+    collector
+}
+
+// reset first so deleted courses drop out instead of keeping their last value
+fn set_by_course(gauge: &IntGaugeVec, counts: Vec<(String, i64)>) {
+    gauge.reset();
+    for (course, count) in counts {
+        gauge.with_label_values(&[&course]).set(count);
+    }
+}
+
+pub fn content_type() -> &'static str {
+    prometheus::TEXT_FORMAT
+}
+
+// r2d2 reports every checkout, so wait times are seen without touching the call sites
+#[derive(Debug)]
+pub struct PoolMetrics;
+
+impl HandleEvent for PoolMetrics {
+    fn handle_checkout(&self, event: CheckoutEvent) {
+        METRICS.pool_wait.observe(event.duration().as_secs_f64());
+    }
+
+    fn handle_timeout(&self, _event: TimeoutEvent) {
+        METRICS.pool_timeouts.inc();
+    }
+}
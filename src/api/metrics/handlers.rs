@@ -0,0 +1,122 @@
+use actix_web::{get, HttpResponse, Responder, Scope, web};
+
+use crate::api::auth::Principal;
+use crate::api::errors::ApiError;
+use crate::api::metrics::collectors::{self, DomainCounts, METRICS};
+use crate::api::policy;
+use crate::db;
+use crate::db::courses::db_functions as courses_db_functions;
+use crate::db::students::db_functions as students_db_functions;
+use crate::db::students_courses::db_functions as students_courses_db_functions;
+
+pub fn metrics_api_scope() -> Scope {
+    web::scope("/metrics")
+        .service(get_metrics)
+}
+
+#[get("")]
+async fn get_metrics(data: web::Data<db::DbPool>, principal: Principal) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::METRICS)?;
+    // read before the domain query checks out a connection of its own
+    METRICS.set_pool_state(data.state(), data.max_size());
+    let counts = db::run(&data, |conn| {
+        Ok(DomainCounts {
+            students: students_db_functions::count_students(conn)?,
+            courses: courses_db_functions::count_courses(conn)?,
+            enrolled_by_course: students_courses_db_functions::count_enrolled_by_course(conn)?,
+            waitlisted_by_course: students_courses_db_functions::count_waitlisted_by_course(conn)?,
+        })
+    }).await;
+    // an exhausted pool is exactly when the pool and request metrics matter, so they are served even if the domain counts fail
+    if let Ok(counts) = counts {
+        METRICS.set_domain_counts(counts);
+    }
+    Ok(HttpResponse::Ok().content_type(collectors::content_type()).body(METRICS.encode()))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{App, test, web};
+    use actix_web::http::StatusCode;
+
+    use crate::api::auth::tests as auth_tests;
+    use crate::api::courses::handlers::tests as courses_tests;
+    use crate::api::courses::types::CreateCourseRequest;
+    use crate::api::metrics::handlers::metrics_api_scope;
+    use crate::api::metrics::middleware::RequestMetrics;
+    use crate::api::students::handlers::student_api_scope;
+    use crate::api::students::handlers::tests as students_tests;
+    use crate::api::students::types::CreateStudentRequest;
+    use crate::db::api_keys::models::Role;
+    use crate::db::courses::db_functions as courses_db_functions;
+    use crate::db::initialize_db_pool;
+    use crate::db::students::db_functions as students_db_functions;
+    use crate::db::students_courses::db_functions as students_courses_db_functions;
+
+    #[actix_web::test]
+    async fn test_metrics() {
+        let pool = initialize_db_pool();
+        let student = CreateStudentRequest { email: String::from("metrics_user@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), student.clone().into());
+        let course = CreateCourseRequest { name: String::from("metrics and monitoring"), ..Default::default() };
+        courses_tests::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        {
+            let mut conn = pool.get().unwrap();
+            let student_id = students_db_functions::get_student(&mut conn, &student.email).unwrap().id;
+            let course_id = courses_db_functions::get_course(&mut conn, &course.name).unwrap().id;
+            students_courses_db_functions::tests::sign_up_for_course(&mut conn, student_id, course_id).unwrap();
+        }
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .wrap(RequestMetrics)
+                .service(student_api_scope())
+                .service(metrics_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get().uri("/students/list").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+        let req = test::TestRequest::get().uri("/students/not-a-number").to_request();
+        test::call_service(&app, req).await;
+
+        let req = test::TestRequest::get().uri("/metrics").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().get("content-type").unwrap().to_str().unwrap().starts_with("text/plain"));
+        let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+        assert!(body.contains(r#"http_requests_total{method="GET",route="/students/list",status="200"}"#));
+        assert!(body.contains(r#"http_request_duration_seconds_bucket{method="GET",route="/students/{id}","#));
+        assert!(body.contains("db_pool_max_connections 10"));
+        assert!(body.contains("db_pool_wait_seconds_count"));
+        assert!(body.contains(r#"course_enrollments{course="metrics and monitoring"} 1"#));
+        assert!(body.contains(r#"course_waitlist{course="metrics and monitoring"} 0"#));
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &student.email);
+        courses_tests::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+    }
+
+    #[actix_web::test]
+    async fn test_metrics_require_admin_or_monitor() {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(initialize_db_pool()))
+                .wrap_fn(|req, srv| auth_tests::as_principal(req, srv, "prometheus", Role::Monitor))
+                .service(metrics_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get().uri("/metrics").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(initialize_db_pool()))
+                .wrap_fn(|req, srv| auth_tests::as_principal(req, srv, "registrar@gmail.com", Role::Registrar))
+                .service(metrics_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get().uri("/metrics").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod collectors;
+pub mod handlers;
+pub mod middleware;
@@ -1,5 +1,18 @@
 pub mod students_courses;
+pub mod audit;
+pub mod auth;
+pub mod health;
 pub mod students;
 pub mod courses;
+pub mod terms;
+pub mod instructors;
+pub mod metrics;
+pub mod search;
+pub mod calendar;
 pub mod errors;
+pub mod exports;
+pub mod imports;
+pub mod pagination;
+pub mod policy;
+pub mod request_id;
 pub mod utils;
\ No newline at end of file
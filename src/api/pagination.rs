@@ -0,0 +1,72 @@
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use actix_web::body::BoxBody;
+use actix_web::http::header::ContentType;
+use serde::Serialize;
+
+use crate::api::errors::{ApiError, FieldError};
+
+pub const DEFAULT_PAGE_LIMIT: i64 = 20;
+pub const MAX_PAGE_LIMIT: i64 = 100;
+
+#[derive(Serialize)]
+pub struct PageResponse<T: Serialize> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<i32>,
+    pub total: i64,
+}
+
+impl<T: Serialize> Responder for PageResponse<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = serde_json::to_string(&self).unwrap();
+
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+}
+
+pub fn page_bounds(limit: Option<i64>, offset: Option<i64>, cursor: Option<i32>) -> Result<(i64, i64), ApiError> {
+    let mut errors = vec![];
+    if cursor.is_some() && offset.is_some() { errors.push(FieldError::new("cursor", "cannot be combined with offset")); }
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+    let offset = offset.unwrap_or(0);
+    if !(1..=MAX_PAGE_LIMIT).contains(&limit) { errors.push(FieldError::new("limit", &format!("must be between 1 and {}", MAX_PAGE_LIMIT))); }
+    if offset < 0 { errors.push(FieldError::new("offset", "must not be negative")); }
+    if !errors.is_empty() { return Err(ApiError::BadClientData(errors)); }
+    Ok((limit, offset))
+}
+
+pub fn next_cursor(ids: &[i32], limit: i64) -> Option<i32> {
+    if ids.len() as i64 == limit { ids.last().copied() } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::pagination::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT, next_cursor, page_bounds};
+
+    #[test]
+    fn test_page_bounds_defaults() {
+        assert_eq!(page_bounds(None, None, None).unwrap(), (DEFAULT_PAGE_LIMIT, 0));
+    }
+
+    #[test]
+    fn test_page_bounds_out_of_range() {
+        assert!(page_bounds(Some(0), None, None).is_err());
+        assert!(page_bounds(Some(MAX_PAGE_LIMIT + 1), None, None).is_err());
+        assert!(page_bounds(None, Some(-1), None).is_err());
+    }
+
+    #[test]
+    fn test_page_bounds_cursor_with_offset() {
+        assert_eq!(page_bounds(None, None, Some(5)).unwrap(), (DEFAULT_PAGE_LIMIT, 0));
+        assert!(page_bounds(None, Some(0), Some(5)).is_err());
+    }
+
+    #[test]
+    fn test_next_cursor() {
+        assert_eq!(next_cursor(&[1, 2, 3], 3), Some(3));
+        assert_eq!(next_cursor(&[1, 2], 3), None);
+    }
+}
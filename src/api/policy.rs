@@ -0,0 +1,79 @@
+use diesel::PgConnection;
+
+use crate::api::auth::Principal;
+use crate::api::errors::ApiError;
+use crate::db::api_keys::models::Role;
+use crate::db::courses::models::Course;
+use crate::db::instructors::db_functions as instructors_db_functions;
+
+pub const ADMIN: &[Role] = &[Role::Admin];
+pub const STAFF: &[Role] = &[Role::Admin, Role::Registrar];
+pub const METRICS: &[Role] = &[Role::Admin, Role::Monitor];
+// monitor keys are handed to scrapers, so they are good for nothing else
+pub const MONITOR_PATHS: &[&str] = &["/metrics"];
+
+pub fn require_role(principal: &Principal, roles: &[Role]) -> Result<(), ApiError> {
+    if roles.contains(&principal.role) { Ok(()) } else { Err(forbidden(principal)) }
+}
+
+pub fn require_path_access(principal: &Principal, path: &str) -> Result<(), ApiError> {
+    if principal.role == Role::Monitor && !MONITOR_PATHS.contains(&path) { Err(forbidden(principal)) } else { Ok(()) }
+}
+
+// students are matched to their record through the email they authenticate with
+pub fn require_student_access(principal: &Principal, student_email: &str) -> Result<(), ApiError> {
+    match principal.role {
+        Role::Admin | Role::Registrar => Ok(()),
+        Role::Student if principal.subject.eq_ignore_ascii_case(student_email) => Ok(()),
+        _ => Err(forbidden(principal)),
+    }
+}
+
+// instructors only see the rosters of courses they are assigned to
+pub fn require_roster_access(conn: &mut PgConnection, principal: &Principal, course: &Course) -> Result<(), ApiError> {
+    match principal.role {
+        Role::Admin | Role::Registrar => Ok(()),
+        Role::Instructor if instructors_db_functions::is_assigned_to_course(conn, &principal.subject, course.id)? => Ok(()),
+        _ => Err(forbidden(principal)),
+    }
+}
+
+fn forbidden(principal: &Principal) -> ApiError {
+    ApiError::Forbidden(format!("role {} is not allowed to perform this action", principal.role.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::auth::{AuthMethod, Principal};
+    use crate::api::policy::{ADMIN, METRICS, require_path_access, require_role, require_student_access, STAFF};
+    use crate::db::api_keys::models::Role;
+
+    fn principal(subject: &str, role: Role) -> Principal {
+        Principal { subject: String::from(subject), method: AuthMethod::Jwt, role }
+    }
+
+    #[test]
+    fn test_require_role() {
+        assert!(require_role(&principal("admin@gmail.com", Role::Admin), ADMIN).is_ok());
+        assert!(require_role(&principal("registrar@gmail.com", Role::Registrar), STAFF).is_ok());
+        assert!(require_role(&principal("registrar@gmail.com", Role::Registrar), ADMIN).is_err());
+        assert!(require_role(&principal("student@gmail.com", Role::Student), STAFF).is_err());
+    }
+
+    #[test]
+    fn test_require_student_access() {
+        assert!(require_student_access(&principal("student@gmail.com", Role::Student), "Student@gmail.com").is_ok());
+        assert!(require_student_access(&principal("student@gmail.com", Role::Student), "other@gmail.com").is_err());
+        assert!(require_student_access(&principal("student@gmail.com", Role::Instructor), "student@gmail.com").is_err());
+        assert!(require_student_access(&principal("registrar@gmail.com", Role::Registrar), "student@gmail.com").is_ok());
+    }
+
+    #[test]
+    fn test_require_path_access() {
+        assert!(require_path_access(&principal("prometheus", Role::Monitor), "/metrics").is_ok());
+        assert!(require_path_access(&principal("prometheus", Role::Monitor), "/courses/list").is_err());
+        assert!(require_path_access(&principal("admin@gmail.com", Role::Admin), "/courses/list").is_ok());
+        assert!(require_role(&principal("prometheus", Role::Monitor), METRICS).is_ok());
+        assert!(require_role(&principal("prometheus", Role::Monitor), STAFF).is_err());
+    }
+}
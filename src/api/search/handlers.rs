@@ -0,0 +1,133 @@
+use actix_web::{get, HttpResponse, Responder, Scope, web};
+
+use crate::api::auth::Principal;
+use crate::api::errors::ApiError;
+use crate::api::pagination;
+use crate::api::policy;
+use crate::api::search::types::{SearchHit, SearchKind, SearchRequest};
+use crate::db;
+use crate::db::search::db_functions;
+
+pub const MAX_QUERY_LENGTH: usize = 100;
+
+pub fn search_api_scope() -> Scope {
+    web::scope("/search")
+        .service(search)
+}
+
+#[get("")]
+async fn search(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<SearchRequest>) -> Result<impl Responder, ApiError> {
+    let term = params.q.trim().to_string();
+    if term.is_empty() { return Err(ApiError::bad_field("q", "must not be empty")); }
+    if term.chars().count() > MAX_QUERY_LENGTH { return Err(ApiError::bad_field("q", &format!("must not be longer than {} characters", MAX_QUERY_LENGTH))); }
+    let (limit, _) = pagination::page_bounds(params.limit, None, None)?;
+    let kind = params.kind;
+    let mut hits = db::run(&data, move |conn| {
+        let mut hits: Vec<SearchHit> = vec![];
+        if kind != Some(SearchKind::Course) {
+            // staff see every student, students only their own record and instructors none
+            hits.extend(db_functions::search_students(conn, &term, limit)?.into_iter()
+                .filter(|x| policy::require_student_access(&principal, &x.student.email).is_ok())
+                .map(SearchHit::from));
+        }
+        if kind != Some(SearchKind::Student) {
+            hits.extend(db_functions::search_courses(conn, &term, limit)?.into_iter().map(SearchHit::from));
+        }
+        Ok(hits)
+    }).await?;
+    // both lists are already ranked, a stable sort keeps students ahead of courses on equal scores
+    hits.sort_by(|a, b| b.score().total_cmp(&a.score()));
+    hits.truncate(limit as usize);
+    Ok(HttpResponse::Ok().json(hits))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{App, test, web};
+    use actix_web::http::StatusCode;
+
+    use crate::api::auth::tests as auth_tests;
+    use crate::api::courses::handlers::tests as courses_test;
+    use crate::api::courses::types::CreateCourseRequest;
+    use crate::api::search::handlers::search_api_scope;
+    use crate::api::students::handlers::tests as students_tests;
+    use crate::api::students::types::CreateStudentRequest;
+    use crate::db::api_keys::models::Role;
+    use crate::db::initialize_db_pool;
+
+    #[actix_web::test]
+    async fn test_search_mixed_results() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("zephyrine_searcher@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let course = CreateCourseRequest { name: String::from("zephyrine studies"), course_desc: Some(String::from("winds of the west")), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(search_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get()
+            .uri("/search?q=Zephyrine")
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let hits = resp.as_array().unwrap();
+        assert_eq!(2, hits.len());
+        assert_eq!(hits[0]["type"], "student");
+        assert_eq!(hits[0]["email"], request.email.as_str());
+        assert_eq!(hits[1]["type"], "course");
+        assert_eq!(hits[1]["name"], course.name.as_str());
+
+        let req = test::TestRequest::get()
+            .uri("/search?q=zephyrine%20studys&type=course")
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp[0]["name"], course.name.as_str());
+        assert!(resp.as_array().unwrap().iter().all(|x| x["type"] == "course"));
+
+        let req = test::TestRequest::get()
+            .uri("/search?q=%20")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+    }
+
+    #[actix_web::test]
+    async fn test_search_student_hits_follow_policy() {
+        let pool = initialize_db_pool();
+        let own = CreateStudentRequest { email: String::from("quillonia_one@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), own.clone().into());
+        let other = CreateStudentRequest { email: String::from("quillonia_two@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), other.clone().into());
+
+        for (email, role, expected) in [
+            ("quillonia_one@gmail.com", Role::Student, vec!["quillonia_one@gmail.com"]),
+            ("quillonia_instructor@gmail.com", Role::Instructor, vec![]),
+            ("registrar@gmail.com", Role::Registrar, vec!["quillonia_one@gmail.com", "quillonia_two@gmail.com"]),
+        ] {
+            let app = test::init_service(
+                App::new()
+                    .app_data(web::Data::new(pool.clone()))
+                    .wrap_fn(move |req, srv| auth_tests::as_principal(req, srv, email, role))
+                    .service(search_api_scope())
+            ).await;
+
+            let req = test::TestRequest::get()
+                .uri("/search?q=quillonia&type=student")
+                .to_request();
+            let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+            let mut emails: Vec<&str> = resp.as_array().unwrap().iter().map(|x| x["email"].as_str().unwrap()).collect();
+            emails.sort();
+            assert_eq!(expected, emails, "{}", email);
+        }
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &own.email);
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &other.email);
+    }
+}
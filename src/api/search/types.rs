@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::courses::types::CourseResponse;
+use crate::api::students::types::StudentResponse;
+use crate::db::search::models::{CourseMatch, StudentMatch};
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    Student,
+    Course,
+}
+
+#[derive(Deserialize)]
+pub struct SearchRequest {
+    pub q: String,
+    pub limit: Option<i64>,
+    #[serde(rename = "type")]
+    pub kind: Option<SearchKind>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SearchHit {
+    Student {
+        score: f32,
+        #[serde(flatten)]
+        student: StudentResponse,
+    },
+    Course {
+        score: f32,
+        #[serde(flatten)]
+        course: CourseResponse,
+    },
+}
+
+impl SearchHit {
+    pub fn score(&self) -> f32 {
+        match self {
+            SearchHit::Student { score, .. } | SearchHit::Course { score, .. } => *score,
+        }
+    }
+}
+
+impl From<StudentMatch> for SearchHit {
+    fn from(value: StudentMatch) -> Self {
+        SearchHit::Student { score: value.score, student: StudentResponse::from(value.student) }
+    }
+}
+
+impl From<CourseMatch> for SearchHit {
+    fn from(value: CourseMatch) -> Self {
+        SearchHit::Course { score: value.score, course: CourseResponse::from(value.course) }
+    }
+}
@@ -1,44 +1,281 @@
-use actix_web::{get, post, Responder, Scope, web};
+use actix_multipart::Multipart;
+use actix_web::{delete, get, HttpResponse, patch, post, Responder, Scope, web};
+use chrono::Local;
+use diesel::{Connection, PgConnection};
 
-use crate::api::errors::ApiError;
-use crate::api::students::types::{CreateStudentRequest, GetStudentRequest, StudentResponse};
+use crate::api::auth::Principal;
+use crate::api::calendar;
+use crate::api::calendar::WeeklyEvent;
+
+use crate::api::errors::{ApiError, FieldError};
+use crate::api::exports;
+use crate::api::exports::ExportRequest;
+use crate::api::imports;
+use crate::api::imports::ImportRequest;
+use crate::api::pagination;
+use crate::api::policy;
+use crate::api::pagination::PageResponse;
+use crate::api::students::types::{CreateStudentRequest, DeleteStudentByIdRequest, DeleteStudentRequest, GetStudentCoursesRequest, GetStudentRequest, GetStudentScheduleRequest, ListStudentsRequest, ScheduleResponse, StudentResponse, TranscriptResponse, UpdateStudentRequest};
+use crate::api::students_courses::types::{EnrolledCourseResponse, StudentCourseFields};
 use crate::api::utils;
 use crate::db;
+use crate::db::course_meetings::db_functions as course_meetings_db_functions;
+use crate::db::courses::db_functions as courses_db_functions;
+use crate::db::courses::models::Course;
 use crate::db::students::db_functions;
-use crate::db::students::models::NewStudent;
+use crate::db::students::models::{NewStudent, Student, StudentSortField, UpdateStudent};
+use crate::db::students_courses::db_functions as students_courses_db_functions;
+use crate::db::students_courses::models::{EnrollmentStatus, StudentCourse};
+use crate::db::terms::db_functions as terms_db_functions;
 
 pub fn student_api_scope() -> Scope {
     web::scope("/students")
         .service(get_student)
+        .service(list_students)
+        .service(export_students)
+        .service(get_student_by_id)
+        .service(get_student_courses)
         .service(create_student)
+        .service(import_students)
+        .service(update_student)
+        .service(delete_student)
+        .service(delete_student_by_id)
+        .service(get_student_transcript)
+        .service(get_student_schedule)
+        .service(get_student_schedule_calendar)
 }
 
 #[get("")]
-async fn get_student(data: web::Data<db::DbPool>, params: web::Query<GetStudentRequest>) -> Result<impl Responder, ApiError> {
-    let mut connection = data.get().unwrap();
-    let student = db_functions::get_student(&mut connection, &params.email)?;
+async fn get_student(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<GetStudentRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_student_access(&principal, &params.email)?;
+    let student = db::run(&data, move |conn| Ok(db_functions::get_student(conn, &params.email)?)).await?;
+    Ok(StudentResponse::from(student))
+}
+
+#[get("/list")]
+async fn list_students(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<ListStudentsRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let (limit, offset) = pagination::page_bounds(params.limit, params.offset, params.cursor)?;
+    if params.cursor.is_some() && params.sort != StudentSortField::Id { return Err(ApiError::bad_field("cursor", "cursor pagination requires sort=id")); }
+    let (sort, cursor) = (params.sort, params.cursor);
+    let (students, total) = db::run(&data, move |conn| {
+        let students = db_functions::list_students(conn, sort, limit, offset, cursor)?;
+        let total = db_functions::count_students(conn)?;
+        Ok((students, total))
+    }).await?;
+    let next_cursor = match sort {
+        StudentSortField::Id => pagination::next_cursor(&students.iter().map(|x| x.id).collect::<Vec<i32>>(), limit),
+        _ => None,
+    };
+    Ok(PageResponse { items: students.into_iter().map(StudentResponse::from).collect(), next_cursor, total })
+}
+
+#[get("/export")]
+async fn export_students(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<ExportRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    if params.student_id.is_some() { return Err(ApiError::bad_field("student_id", "filter is not supported for students")); }
+    let course_id = params.course_id;
+    if let Some(course_id) = course_id {
+        db::run(&data, move |conn| Ok(courses_db_functions::get_course_by_id(conn, course_id)?)).await?;
+    }
+    Ok(exports::stream_export(data.get_ref().clone(), params.format, "students", &["id", "email", "phone_number", "created_at", "updated_at"], move |conn, after_id, limit| {
+        let students = db_functions::list_students_for_export(conn, course_id, after_id, limit)?;
+        Ok(students.into_iter().map(|x| (x.id, StudentResponse::from(x))).collect())
+    }))
+}
+
+#[get("/{id}")]
+async fn get_student_by_id(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>) -> Result<impl Responder, ApiError> {
+    let student = db::run(&data, move |conn| {
+        let student = db_functions::get_student_by_id(conn, path.into_inner())?;
+        policy::require_student_access(&principal, &student.email)?;
+        Ok(student)
+    }).await?;
     Ok(StudentResponse::from(student))
 }
 
+#[get("/{id}/courses")]
+async fn get_student_courses(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>, params: web::Query<GetStudentCoursesRequest>) -> Result<impl Responder, ApiError> {
+    let status = params.status;
+    let enrollments = db::run(&data, move |conn| {
+        let student = db_functions::get_student_by_id(conn, path.into_inner())?;
+        policy::require_student_access(&principal, &student.email)?;
+        Ok(students_courses_db_functions::get_enrollments_for_student(conn, &student, status)?)
+    }).await?;
+    match params.fields {
+        Some(StudentCourseFields::Name) => Ok(HttpResponse::Ok().json(enrollments.into_iter().map(|(_, x)| x.name).collect::<Vec<String>>())),
+        None => Ok(HttpResponse::Ok().json(enrollments.into_iter().map(EnrolledCourseResponse::from).collect::<Vec<EnrolledCourseResponse>>())),
+    }
+}
+
 #[post("")]
-async fn create_student(data: web::Data<db::DbPool>, req: web::Json<CreateStudentRequest>) -> Result<impl Responder, ApiError> {
-    let mut connection = data.get().unwrap();
-    let new_email = &req.email;
-    if !utils::validate_email(new_email.as_str()) { return Err(ApiError::BadClientData); }
-    let student = db_functions::create_student(&mut connection, NewStudent::from(req.0))?;
+async fn create_student(data: web::Data<db::DbPool>, principal: Principal, req: web::Json<CreateStudentRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let errors = validate_new_student(&req);
+    if !errors.is_empty() { return Err(ApiError::BadClientData(errors)); }
+    let student = db::run(&data, move |conn| Ok(db_functions::create_student(conn, NewStudent::from(req.0))?)).await?;
+    Ok(StudentResponse::from(student))
+}
+
+#[post("/import")]
+async fn import_students(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<ImportRequest>, payload: Multipart) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let bytes = imports::read_csv_upload(payload).await?;
+    let rows = imports::parse_csv::<CreateStudentRequest>(&bytes)?;
+    let mode = params.mode;
+    db::run(&data, move |conn| {
+        imports::run_import(conn, mode, rows, "email", validate_new_student, |conn, row| {
+            db_functions::create_student(conn, NewStudent::from(row)).map(|x| x.id)
+        })
+    }).await
+}
+
+fn validate_new_student(req: &CreateStudentRequest) -> Vec<FieldError> {
+    let mut errors = vec![];
+    if !utils::validate_email(req.email.as_str()) { errors.push(FieldError::new("email", "invalid email format")); }
+    if req.phone_number.as_ref().is_some_and(|x| !utils::validate_phone_number(x)) { errors.push(FieldError::new("phone_number", "invalid phone number format")); }
+    errors
+}
+
+#[patch("/{id}")]
+async fn update_student(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>, req: web::Json<UpdateStudentRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    if let Some(new_email) = &req.email {
+        if !utils::validate_email(new_email.as_str()) { return Err(ApiError::bad_field("email", "invalid email format")); }
+    }
+    if let Some(Some(phone_number)) = &req.phone_number {
+        if !utils::validate_phone_number(phone_number) { return Err(ApiError::bad_field("phone_number", "invalid phone number format")); }
+    }
+    let student = db::run(&data, move |conn| Ok(db_functions::update_student(conn, path.into_inner(), UpdateStudent::from(req.0))?)).await?;
     Ok(StudentResponse::from(student))
 }
 
+#[delete("")]
+async fn delete_student(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<DeleteStudentRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::ADMIN)?;
+    db::run(&data, move |conn| {
+        conn.transaction::<_, ApiError, _>(|conn| {
+            let student = db_functions::get_student(conn, &params.email)?;
+            remove_student(conn, &student, params.cascade)
+        })
+    }).await?;
+    Ok(HttpResponse::Ok().body("student deleted successfully"))
+}
+
+#[delete("/{id}")]
+async fn delete_student_by_id(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>, params: web::Query<DeleteStudentByIdRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::ADMIN)?;
+    db::run(&data, move |conn| {
+        conn.transaction::<_, ApiError, _>(|conn| {
+            let student = db_functions::get_student_by_id(conn, path.into_inner())?;
+            remove_student(conn, &student, params.cascade)
+        })
+    }).await?;
+    Ok(HttpResponse::Ok().body("student deleted successfully"))
+}
+
+fn remove_student(conn: &mut PgConnection, student: &Student, cascade: bool) -> Result<(), ApiError> {
+    // dropped enrollments never block, they are removed along with the student
+    let courses = students_courses_db_functions::get_courses_attended_by_student(conn, &student.email)?
+        .into_iter()
+        .filter(|(enrollment, _)| enrollment.status != EnrollmentStatus::Dropped)
+        .map(|(_, course)| course)
+        .collect::<Vec<Course>>();
+    if !courses.is_empty() && !cascade {
+        return Err(ApiError::BlockingEnrollments(courses.iter().map(|x| x.name.clone()).collect()));
+    }
+    students_courses_db_functions::delete_student_courses_for_student(conn, student.id)?;
+    students_courses_db_functions::delete_waitlist_entries_for_student(conn, student.id)?;
+    db_functions::delete_student(conn, &student.email)?;
+    for course in courses {
+        students_courses_db_functions::promote_from_waitlist(conn, course.id)?;
+    }
+    Ok(())
+}
+
+#[get("/{id}/transcript")]
+async fn get_student_transcript(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>) -> Result<impl Responder, ApiError> {
+    let transcript = db::run(&data, move |conn| {
+        let student = db_functions::get_student_by_id(conn, path.into_inner())?;
+        policy::require_student_access(&principal, &student.email)?;
+        let graded = students_courses_db_functions::get_courses_attended_by_student(conn, &student.email)?
+            .into_iter()
+            .filter(|(enrollment, _)| matches!(enrollment.status, EnrollmentStatus::Completed | EnrollmentStatus::Failed))
+            .collect::<Vec<(StudentCourse, Course)>>();
+        Ok((student, graded))
+    }).await?;
+    Ok(TranscriptResponse::from(transcript))
+}
+
+#[get("/{id}/schedule")]
+async fn get_student_schedule(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>, params: web::Query<GetStudentScheduleRequest>) -> Result<impl Responder, ApiError> {
+    let schedule = db::run(&data, move |conn| {
+        let student = db_functions::get_student_by_id(conn, path.into_inner())?;
+        policy::require_student_access(&principal, &student.email)?;
+        // classes of different terms never share a week, so the timetable covers one term
+        let term = match params.term_id {
+            Some(term_id) => Some(terms_db_functions::get_term(conn, term_id)?),
+            None => terms_db_functions::get_current_term(conn, Local::now().date_naive())?,
+        };
+        let schedule = match &term {
+            Some(term) => course_meetings_db_functions::get_schedule_for_student(conn, student.id, term.id)?,
+            None => vec![],
+        };
+        Ok((student, term.map(|x| x.id), schedule))
+    }).await?;
+    Ok(ScheduleResponse::from(schedule))
+}
+
+#[get("/{id}/schedule.ics")]
+async fn get_student_schedule_calendar(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>) -> Result<impl Responder, ApiError> {
+    let events = db::run(&data, move |conn| {
+        let student = db_functions::get_student_by_id(conn, path.into_inner())?;
+        policy::require_student_access(&principal, &student.email)?;
+        let mut events = vec![];
+        for (enrollment, course, term) in students_courses_db_functions::get_enrolled_courses_with_terms(conn, student.id)? {
+            for meeting in course_meetings_db_functions::get_meetings_for_offering(conn, enrollment.offering_id)? {
+                events.push(WeeklyEvent {
+                    uid: format!("student-{}-meeting-{}@rust-crud-app-course-management", student.id, meeting.id),
+                    summary: course.name.clone(),
+                    location: meeting.room,
+                    day_of_week: meeting.day_of_week,
+                    start_time: meeting.start_time,
+                    end_time: meeting.end_time,
+                    starts_on: term.starts_on,
+                    ends_on: term.ends_on,
+                });
+            }
+        }
+        Ok(events)
+    }).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("text/calendar; charset=utf-8")
+        .body(calendar::render(&events, Local::now().naive_utc())))
+}
+
 #[cfg(test)]
 pub mod tests {
     use actix_web::{App, test, web};
+    use actix_web::http::StatusCode;
+    use chrono::NaiveTime;
     use diesel::PgConnection;
 
+    use crate::api::auth::tests as auth_tests;
+    use crate::api::courses::handlers::tests as courses_tests;
+    use crate::api::courses::types::CreateCourseRequest;
+    use crate::api::imports::tests as imports_tests;
+    use crate::api::request_id::{REQUEST_ID_HEADER, RequestId};
     use crate::api::students::handlers::student_api_scope;
-    use crate::api::students::types::CreateStudentRequest;
+    use crate::api::students::types::{CreateStudentRequest, UpdateStudentRequest};
+    use crate::db::course_meetings::db_functions as course_meetings_db_functions;
+    use crate::db::course_meetings::models::{DayOfWeek, NewCourseMeeting};
+    use crate::db::course_offerings::db_functions as course_offerings_db_functions;
     use crate::db::initialize_db_pool;
     use crate::db::students::db_functions;
+    use crate::db::courses::db_functions as courses_db_functions;
     use crate::db::students::models::NewStudent;
+    use crate::db::students_courses::db_functions as students_courses_db_functions;
+    use crate::db::students_courses::models::EnrollmentStatus;
 
     #[actix_web::test]
     async fn test_create_student_happy_path() {
@@ -49,6 +286,7 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(student_api_scope())
         ).await;
 
@@ -71,6 +309,7 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(student_api_scope())
         ).await;
 
@@ -93,6 +332,7 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(student_api_scope())
         ).await;
 
@@ -116,6 +356,7 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(student_api_scope())
         ).await;
 
@@ -137,6 +378,7 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(student_api_scope())
         ).await;
 
@@ -149,6 +391,424 @@ pub mod tests {
         cleanup(&mut pool.clone().get().unwrap(), &request.email);
     }
 
+    #[actix_web::test]
+    async fn test_get_student_not_found_error_body() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("sample_user_twelve@gmail.com"), ..Default::default() };
+        setup_existing_student(false, &mut pool.clone().get().unwrap(), request.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .wrap(RequestId)
+                .service(student_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students?email={}", &request.email))
+            .insert_header((REQUEST_ID_HEADER, "test-request-id"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(resp.headers().get(REQUEST_ID_HEADER).unwrap(), "test-request-id");
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["code"], "not_found");
+        assert_eq!(body["message"], "Record not found");
+        assert_eq!(body["request_id"], "test-request-id");
+    }
+
+    #[actix_web::test]
+    async fn test_update_student_happy_path() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("sample_user_five@gmail.com"), ..Default::default() };
+        let update = UpdateStudentRequest { email: Some(String::from("sample_user_five_updated@gmail.com")), phone_number: Some(Some(String::from("91234567"))) };
+        setup_existing_student(false, &mut pool.clone().get().unwrap(), CreateStudentRequest { email: update.email.clone().unwrap(), ..Default::default() }.into());
+        setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let student = db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(student_api_scope())
+        ).await;
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("/students/{}", student.id))
+            .set_json(update.clone())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("/students/{}", student.id))
+            .set_json(serde_json::json!({}))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["phone_number"], "91234567");
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("/students/{}", student.id))
+            .set_json(serde_json::json!({ "phone_number": null }))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["phone_number"], serde_json::Value::Null);
+
+        cleanup(&mut pool.clone().get().unwrap(), &update.email.unwrap());
+    }
+
+    #[actix_web::test]
+    async fn test_update_student_invalid_email_format() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("sample_user_six@gmail.com"), ..Default::default() };
+        setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let student = db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(student_api_scope())
+        ).await;
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("/students/{}", student.id))
+            .set_json(UpdateStudentRequest { email: Some(String::from("invalid email")), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["details"][0]["field"], "email");
+
+        cleanup(&mut pool.clone().get().unwrap(), &request.email);
+    }
+
+    #[actix_web::test]
+    async fn test_update_student_duplicate_email() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("sample_user_seven@gmail.com"), ..Default::default() };
+        let other = CreateStudentRequest { email: String::from("sample_user_eight@gmail.com"), ..Default::default() };
+        setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        setup_existing_student(true, &mut pool.clone().get().unwrap(), other.clone().into());
+        let student = db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(student_api_scope())
+        ).await;
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("/students/{}", student.id))
+            .set_json(UpdateStudentRequest { email: Some(other.email.clone()), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        cleanup(&mut pool.clone().get().unwrap(), &other.email);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_student_happy_path() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("sample_user_nine@gmail.com"), ..Default::default() };
+        setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(student_api_scope())
+        ).await;
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/students?email={}", &request.email))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert!(db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_delete_student_with_enrollments() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("sample_user_ten@gmail.com"), ..Default::default() };
+        setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let course = CreateCourseRequest { name: String::from("sample_course_one"), ..Default::default() };
+        courses_tests::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let student_id = db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).unwrap().id;
+        let course_id = courses_db_functions::get_course(&mut pool.clone().get().unwrap(), &course.name).unwrap().id;
+        students_courses_db_functions::tests::sign_up_for_course(&mut pool.clone().get().unwrap(), student_id, course_id).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(student_api_scope())
+        ).await;
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/students?email={}", &request.email))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/students?email={}&cascade=true", &request.email))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert!(db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).is_err());
+
+        courses_tests::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_student_with_dropped_enrollment() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("sample_user_fifteen@gmail.com"), ..Default::default() };
+        setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let course = CreateCourseRequest { name: String::from("sample_course_three"), ..Default::default() };
+        courses_tests::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let student_id = db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).unwrap().id;
+        let course_id = courses_db_functions::get_course(&mut pool.clone().get().unwrap(), &course.name).unwrap().id;
+        let offering_id = course_offerings_db_functions::tests::open_offering(&mut pool.clone().get().unwrap(), course_id).unwrap().id;
+        students_courses_db_functions::tests::sign_up_for_course(&mut pool.clone().get().unwrap(), student_id, course_id).unwrap();
+        students_courses_db_functions::update_student_course_status(&mut pool.clone().get().unwrap(), student_id, offering_id, EnrollmentStatus::Dropped).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(student_api_scope())
+        ).await;
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/students/{}", student_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert!(db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).is_err());
+
+        courses_tests::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+    }
+
+    #[actix_web::test]
+    async fn test_student_routes_by_id() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("sample_user_fourteen@gmail.com"), ..Default::default() };
+        setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let course = CreateCourseRequest { name: String::from("sample_course_two"), ..Default::default() };
+        courses_tests::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let student_id = db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).unwrap().id;
+        let course_id = courses_db_functions::get_course(&mut pool.clone().get().unwrap(), &course.name).unwrap().id;
+        students_courses_db_functions::tests::sign_up_for_course(&mut pool.clone().get().unwrap(), student_id, course_id).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(student_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students/{}", student_id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["email"], request.email.as_str());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students/{}/courses", student_id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp[0]["name"], course.name.as_str());
+        assert_eq!(resp[0]["enrollment"]["status"], "enrolled");
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students/{}/courses?fields=name&status=dropped", student_id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, serde_json::json!([]));
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/students/{}", student_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/students/{}?cascade=true", student_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students/{}", student_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        courses_tests::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+    }
+
+    #[actix_web::test]
+    async fn test_list_students_happy_path() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("sample_user_eleven@gmail.com"), ..Default::default() };
+        setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(student_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get()
+            .uri("/students/list?limit=1&cursor=0")
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["items"].as_array().unwrap().len(), 1);
+        assert!(resp["next_cursor"].is_i64());
+        assert!(resp["total"].as_i64().unwrap() >= 1);
+
+        cleanup(&mut pool.clone().get().unwrap(), &request.email);
+    }
+
+    #[actix_web::test]
+    async fn test_list_students_invalid_params() {
+        let pool = initialize_db_pool();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(student_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get()
+            .uri("/students/list?limit=0")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::get()
+            .uri("/students/list?sort=email&cursor=1")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::get()
+            .uri("/students/list?offset=10&cursor=1")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_get_student_schedule_calendar() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("sample_user_thirteen@gmail.com"), ..Default::default() };
+        setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let course = CreateCourseRequest { name: String::from("calligraphy"), ..Default::default() };
+        courses_tests::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let student_id = db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).unwrap().id;
+        let course_id = courses_db_functions::get_course(&mut pool.clone().get().unwrap(), &course.name).unwrap().id;
+        let offering = course_offerings_db_functions::tests::open_offering(&mut pool.clone().get().unwrap(), course_id).unwrap();
+        let meeting = NewCourseMeeting {
+            course_id,
+            offering_id: offering.id,
+            day_of_week: DayOfWeek::Wednesday,
+            start_time: NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(15, 30, 0).unwrap(),
+            room: Some(String::from("Studio 4")),
+        };
+        course_meetings_db_functions::create_course_meeting(&mut pool.clone().get().unwrap(), meeting).unwrap();
+        students_courses_db_functions::tests::sign_up_for_course(&mut pool.clone().get().unwrap(), student_id, course_id).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(student_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students/{}/schedule.ics", student_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert_eq!(resp.headers().get("content-type").unwrap(), "text/calendar; charset=utf-8");
+        let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+        assert_eq!(1, body.matches("BEGIN:VEVENT").count());
+        assert!(body.contains("RRULE:FREQ=WEEKLY;BYDAY=WE;UNTIL=20261231T235959\r\n"));
+        assert!(body.contains("SUMMARY:calligraphy\r\n"));
+        assert!(body.contains("LOCATION:Studio 4\r\n"));
+
+        cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        courses_tests::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+    }
+
+    #[actix_web::test]
+    async fn test_import_students() {
+        let pool = initialize_db_pool();
+        let emails = ["import_user_one@gmail.com", "import_user_two@gmail.com"];
+        for email in emails {
+            cleanup(&mut pool.clone().get().unwrap(), email);
+        }
+        let csv = format!("email,phone_number\n{},91234567\nnot an email,\n{},\n", emails[0], emails[1]);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(student_api_scope())
+        ).await;
+
+        let (content_type, body) = imports_tests::multipart_csv(&csv);
+        let req = test::TestRequest::post()
+            .uri("/students/import")
+            .insert_header(("content-type", content_type))
+            .set_payload(body)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["code"], "import_rejected");
+        assert_eq!(body["details"]["skipped"], 2);
+        assert_eq!(body["details"]["rows"][1]["line"], 3);
+        assert_eq!(body["details"]["rows"][1]["errors"][0]["field"], "email");
+        assert!(db_functions::get_student(&mut pool.clone().get().unwrap(), emails[0]).is_err());
+
+        let (content_type, body) = imports_tests::multipart_csv(&csv);
+        let req = test::TestRequest::post()
+            .uri("/students/import?mode=skip_invalid")
+            .insert_header(("content-type", content_type))
+            .set_payload(body)
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!((resp["created"].clone(), resp["skipped"].clone()), (serde_json::json!(2), serde_json::json!(1)));
+        assert_eq!(resp["rows"][1]["status"], "skipped");
+        assert!(db_functions::get_student(&mut pool.clone().get().unwrap(), emails[1]).is_ok());
+
+        let (content_type, body) = imports_tests::multipart_csv(&csv);
+        let req = test::TestRequest::post()
+            .uri("/students/import?mode=skip_invalid")
+            .insert_header(("content-type", content_type))
+            .set_payload(body)
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["created"], 0);
+        assert_eq!(resp["rows"][0]["errors"][0]["message"], "already exists");
+
+        for email in emails {
+            cleanup(&mut pool.clone().get().unwrap(), email);
+        }
+    }
+
     pub fn setup_existing_student(should_exist: bool, conn: &mut PgConnection, student: NewStudent) {
         match should_exist {
             true => db_functions::create_student(conn, student).map(|_| ()).expect("setup failed"),
@@ -157,6 +817,10 @@ pub mod tests {
     }
 
     pub fn cleanup(conn: &mut PgConnection, email: &str) {
+        if let Ok(student) = db_functions::get_student(conn, email) {
+            students_courses_db_functions::delete_student_courses_for_student(conn, student.id).expect("cleanup failed");
+            students_courses_db_functions::delete_waitlist_entries_for_student(conn, student.id).expect("cleanup failed");
+        }
         db_functions::delete_student(conn, email).expect("cleanup failed");
     }
 }
\ No newline at end of file
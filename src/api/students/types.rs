@@ -2,21 +2,71 @@ use actix_web::{HttpRequest, HttpResponse, Responder};
 use actix_web::body::BoxBody;
 use actix_web::http::header::ContentType;
 use chrono::{Local, TimeZone};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::db::students::models::Student;
+use crate::api::students_courses::types::StudentCourseFields;
+use crate::api::utils;
+use crate::db::course_meetings::models::{CourseMeeting, DayOfWeek};
+use crate::db::courses::models::Course;
+use crate::db::students::models::{Student, StudentSortField};
+use crate::db::students_courses::models::{EnrollmentStatus, Grade, StudentCourse};
 
 #[derive(Deserialize)]
 pub struct GetStudentRequest {
     pub email: String,
 }
 
+#[derive(Deserialize)]
+pub struct ListStudentsRequest {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    pub cursor: Option<i32>,
+    #[serde(default)]
+    pub sort: StudentSortField,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteStudentRequest {
+    pub email: String,
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteStudentByIdRequest {
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+#[derive(Deserialize)]
+pub struct GetStudentScheduleRequest {
+    pub term_id: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct GetStudentCoursesRequest {
+    pub fields: Option<StudentCourseFields>,
+    pub status: Option<EnrollmentStatus>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct CreateStudentRequest {
     pub email: String,
     pub phone_number: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct UpdateStudentRequest {
+    pub email: Option<String>,
+    // a missing field leaves the number as it is, an explicit null clears it
+    #[serde(default, deserialize_with = "deserialize_present", skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<Option<String>>,
+}
+
+fn deserialize_present<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Option<String>>, D::Error> {
+    Option::<String>::deserialize(deserializer).map(Some)
+}
+
 #[derive(Serialize)]
 pub struct StudentResponse {
     id: i32,
@@ -48,4 +98,122 @@ impl Responder for StudentResponse {
             .content_type(ContentType::json())
             .body(body)
     }
-}
\ No newline at end of file
+}
+
+#[derive(Serialize)]
+pub struct TranscriptEntryResponse {
+    course_id: i32,
+    name: String,
+    credits: i32,
+    status: EnrollmentStatus,
+    grade: Option<Grade>,
+    grade_points: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct TranscriptResponse {
+    student_id: i32,
+    email: String,
+    courses: Vec<TranscriptEntryResponse>,
+    attempted_credits: i32,
+    earned_credits: i32,
+    gpa: Option<f64>,
+}
+
+impl From<(Student, Vec<(StudentCourse, Course)>)> for TranscriptResponse {
+    fn from((student, graded): (Student, Vec<(StudentCourse, Course)>)) -> Self {
+        let attempted_credits = graded.iter().map(|(_, course)| course.credits).sum();
+        let earned_credits = graded.iter()
+            .filter(|(enrollment, _)| enrollment.status == EnrollmentStatus::Completed)
+            .map(|(_, course)| course.credits)
+            .sum();
+        let gpa = utils::compute_gpa(&graded.iter()
+            .filter_map(|(enrollment, course)| enrollment.grade.map(|grade| (grade.points(), course.credits)))
+            .collect::<Vec<(f64, i32)>>());
+        TranscriptResponse {
+            student_id: student.id,
+            email: student.email,
+            courses: graded.into_iter().map(|(enrollment, course)| TranscriptEntryResponse {
+                course_id: course.id,
+                name: course.name,
+                credits: course.credits,
+                status: enrollment.status,
+                grade: enrollment.grade,
+                grade_points: enrollment.grade.map(|grade| grade.points()),
+            }).collect(),
+            attempted_credits,
+            earned_credits,
+            gpa,
+        }
+    }
+}
+
+impl Responder for TranscriptResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = serde_json::to_string(&self).unwrap();
+
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+}
+
+#[derive(Serialize)]
+pub struct ScheduleSlotResponse {
+    course_id: i32,
+    course_name: String,
+    start_time: String,
+    end_time: String,
+    room: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ScheduleDayResponse {
+    day_of_week: DayOfWeek,
+    slots: Vec<ScheduleSlotResponse>,
+}
+
+#[derive(Serialize)]
+pub struct ScheduleResponse {
+    student_id: i32,
+    term_id: Option<i32>,
+    days: Vec<ScheduleDayResponse>,
+}
+
+impl From<(Student, Option<i32>, Vec<(CourseMeeting, Course)>)> for ScheduleResponse {
+    fn from((student, term_id, schedule): (Student, Option<i32>, Vec<(CourseMeeting, Course)>)) -> Self {
+        let mut days: Vec<ScheduleDayResponse> = vec![];
+        for (meeting, course) in schedule {
+            let slot = ScheduleSlotResponse {
+                course_id: course.id,
+                course_name: course.name,
+                start_time: meeting.start_time.format("%H:%M").to_string(),
+                end_time: meeting.end_time.format("%H:%M").to_string(),
+                room: meeting.room,
+            };
+            match days.last_mut() {
+                Some(day) if day.day_of_week == meeting.day_of_week => day.slots.push(slot),
+                _ => days.push(ScheduleDayResponse { day_of_week: meeting.day_of_week, slots: vec![slot] }),
+            }
+        }
+        ScheduleResponse {
+            student_id: student.id,
+            term_id,
+            days,
+        }
+    }
+}
+
+impl Responder for ScheduleResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = serde_json::to_string(&self).unwrap();
+
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+}
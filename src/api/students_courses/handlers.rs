@@ -1,66 +1,329 @@
-use actix_web::{delete, get, HttpResponse, post, Responder, Scope, web};
+use std::collections::HashMap;
 
-use crate::api::errors::ApiError;
-use crate::api::students_courses::types::{CreateStudentCourseRequest, DeleteStudentCourseRequest, GetStudentCourseByCourseRequest, GetStudentCourseByStudentRequest};
+use actix_web::{delete, get, HttpResponse, patch, post, put, Responder, Scope, web};
+use diesel::{Connection, OptionalExtension, PgConnection};
+
+use crate::api::auth::Principal;
+use crate::api::errors::{ApiError, FieldError};
+use crate::api::exports;
+use crate::api::exports::ExportRequest;
+use crate::api::policy;
+use crate::api::students_courses::types::{BatchAction, BatchItemError, BatchItemResult, BatchItemStatus, BatchReport, BatchStudentCourseRequest, CourseStudentFields, CreateStudentCourseRequest, DeleteStudentCourseRequest, EnrolledCourseResponse, EnrollmentExportRow, EnrollmentResponse, EnrolledStudentResponse, GetStudentCourseByCourseRequest, GetStudentCourseByStudentRequest, GetWaitlistRequest, RecordGradesRequest, StudentCourseFields, UpdateStudentCourseStatusRequest, WaitlistEntryResponse};
 use crate::db;
+use crate::db::course_offerings::db_functions as course_offerings_db_functions;
+use crate::db::course_offerings::models::CourseOffering;
 use crate::db::courses::db_functions as courses_db_functions;
+use crate::db::courses::models::Course;
 use crate::db::students::db_functions as students_db_functions;
+use crate::db::students::models::Student;
 use crate::db::students_courses::db_functions as students_courses_db_functions;
+use crate::db::students_courses::models::{EnrollmentOutcome, EnrollmentStatus, StudentCourse};
+use crate::db::terms::db_functions as terms_db_functions;
+
+pub const MAX_BATCH_ITEMS: usize = 500;
 
 pub fn students_courses_api_scope() -> Scope {
     web::scope("/students-courses")
         .service(create_student_course)
         .service(get_courses_for_student)
+        .service(update_student_course_status)
         .service(delete_student_course)
+        .service(hard_delete_student_course)
         .service(fetch_students_in_course)
+        .service(fetch_waitlist_for_course)
+        .service(export_student_courses)
+        .service(record_grades)
+        .service(batch_student_courses)
 }
 
 #[post("")]
-async fn create_student_course(data: web::Data<db::DbPool>, req: web::Json<CreateStudentCourseRequest>) -> Result<impl Responder, ApiError> {
-    let mut connection = data.get().unwrap();
-    let student = students_db_functions::get_student(&mut connection, &req.student_email)?;
-    let course = courses_db_functions::get_course(&mut connection, &req.course_name)?;
-    students_courses_db_functions::create_student_course(&mut connection, student.id, course.id)?;
-    Ok(HttpResponse::Ok().body("student sign up successful"))
+async fn create_student_course(data: web::Data<db::DbPool>, principal: Principal, req: web::Json<CreateStudentCourseRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_student_access(&principal, &req.student_email)?;
+    let outcome = db::run(&data, move |conn| {
+        let student = students_db_functions::get_student(conn, &req.student_email)?;
+        let course = courses_db_functions::get_course(conn, &req.course_name)?;
+        sign_up(conn, &student, &course, req.offering_id)
+    }).await?;
+    match outcome {
+        EnrollmentOutcome::Enrolled(_) => Ok(HttpResponse::Ok().body("student sign up successful")),
+        EnrollmentOutcome::Waitlisted(_) => Ok(HttpResponse::Accepted().body("course is full, student added to waitlist")),
+    }
 }
 
 #[get("/student")]
-async fn get_courses_for_student(data: web::Data<db::DbPool>, params: web::Query<GetStudentCourseByStudentRequest>) -> Result<impl Responder, ApiError> {
-    let mut connection = data.get().unwrap();
-    let courses_vec = students_courses_db_functions::get_courses_attended_by_student(&mut connection, &params.student_email)?;
-    let courses_list = format!("{:?}", courses_vec.into_iter().map(|x| x.name).collect::<Vec<String>>());
-    Ok(HttpResponse::Ok().body(courses_list))
+async fn get_courses_for_student(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<GetStudentCourseByStudentRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_student_access(&principal, &params.student_email)?;
+    let (email, status) = (params.student_email.clone(), params.status);
+    let enrollments = db::run(&data, move |conn| {
+        let student = students_db_functions::get_student(conn, &email)?;
+        Ok(students_courses_db_functions::get_enrollments_for_student(conn, &student, status)?)
+    }).await?;
+    match params.fields {
+        Some(StudentCourseFields::Name) => Ok(HttpResponse::Ok().json(enrollments.into_iter().map(|(_, x)| x.name).collect::<Vec<String>>())),
+        None => Ok(HttpResponse::Ok().json(enrollments.into_iter().map(EnrolledCourseResponse::from).collect::<Vec<EnrolledCourseResponse>>())),
+    }
+}
+
+#[patch("")]
+async fn update_student_course_status(data: web::Data<db::DbPool>, principal: Principal, req: web::Json<UpdateStudentCourseStatusRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let enrollment = db::run(&data, move |conn| {
+        let student = students_db_functions::get_student(conn, &req.student_email)?;
+        let course = courses_db_functions::get_course(conn, &req.course_name)?;
+        transition_status(conn, &student, &course, req.offering_id, req.status)
+    }).await?;
+    Ok(EnrollmentResponse::from(enrollment))
 }
 
 #[delete("")]
-async fn delete_student_course(data: web::Data<db::DbPool>, req: web::Json<DeleteStudentCourseRequest>) -> Result<impl Responder, ApiError> {
-    let mut connection = data.get().unwrap();
-    let student = students_db_functions::get_student(&mut connection, &req.student_email)?;
-    let course = courses_db_functions::get_course(&mut connection, &req.course_name)?;
-    students_courses_db_functions::delete_student_course(&mut connection, student.id, course.id)?;
+async fn delete_student_course(data: web::Data<db::DbPool>, principal: Principal, req: web::Json<DeleteStudentCourseRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_student_access(&principal, &req.student_email)?;
+    db::run(&data, move |conn| {
+        let student = students_db_functions::get_student(conn, &req.student_email)?;
+        let course = courses_db_functions::get_course(conn, &req.course_name)?;
+        drop_sign_up(conn, &student, &course, req.offering_id)
+    }).await?;
     Ok(HttpResponse::Ok().body("sign-up deleted successfully"))
 }
 
+#[delete("/hard")]
+async fn hard_delete_student_course(data: web::Data<db::DbPool>, principal: Principal, req: web::Json<DeleteStudentCourseRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::ADMIN)?;
+    db::run(&data, move |conn| {
+        let student = students_db_functions::get_student(conn, &req.student_email)?;
+        let course = courses_db_functions::get_course(conn, &req.course_name)?;
+        let offering = match req.offering_id {
+            Some(_) => resolve_offering(conn, &course, req.offering_id)?,
+            None => {
+                let mut offering_ids: Vec<i32> = students_courses_db_functions::get_courses_attended_by_student(conn, &student.email)?.into_iter()
+                    .filter(|(_, x)| x.id == course.id)
+                    .map(|(x, _)| x.offering_id)
+                    .collect();
+                offering_ids.extend(students_courses_db_functions::get_waitlist_for_course(conn, &course)?.into_iter()
+                    .filter(|(_, x)| x.id == student.id)
+                    .map(|(x, _)| x.offering_id));
+                offering_ids.sort();
+                offering_ids.dedup();
+                match offering_ids.as_slice() {
+                    [] => resolve_offering(conn, &course, None)?,
+                    [offering_id] => course_offerings_db_functions::get_course_offering(conn, *offering_id)?,
+                    _ => return Err(ApiError::bad_field("offering_id", "student has signed up for several offerings of the course, pick one")),
+                }
+            }
+        };
+        Ok(students_courses_db_functions::delete_student_course(conn, student.id, &offering)?)
+    }).await?;
+    Ok(HttpResponse::Ok().body("sign-up deleted permanently"))
+}
+
+#[put("/grades")]
+async fn record_grades(data: web::Data<db::DbPool>, principal: Principal, req: web::Json<RecordGradesRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let recorded = db::run(&data, move |conn| {
+        let course = courses_db_functions::get_course(conn, &req.course_name)?;
+        conn.transaction::<_, ApiError, _>(|conn| {
+            let mut recorded = vec![];
+            let mut errors = vec![];
+            for (index, entry) in req.grades.iter().enumerate() {
+                let field = format!("grades[{}].student_email", index);
+                let Some(student) = students_db_functions::get_student(conn, &entry.student_email).optional()? else {
+                    errors.push(FieldError::new(&field, "student not found"));
+                    continue;
+                };
+                match find_enrollment(conn, &student, &course, req.offering_id)? {
+                    Some(enrollment) if enrollment.status.can_record_grade(entry.grade) => {
+                        recorded.push(students_courses_db_functions::record_grade(conn, student.id, enrollment.offering_id, entry.grade)?);
+                    }
+                    _ => errors.push(FieldError::new(&field, "student is not enrolled in course")),
+                }
+            }
+            if !errors.is_empty() { return Err(ApiError::BadClientData(errors)); }
+            Ok(recorded)
+        })
+    }).await?;
+    Ok(HttpResponse::Ok().json(recorded.into_iter().map(EnrollmentResponse::from).collect::<Vec<EnrollmentResponse>>()))
+}
+
+#[post("/batch")]
+async fn batch_student_courses(data: web::Data<db::DbPool>, principal: Principal, req: web::Json<BatchStudentCourseRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let req = req.into_inner();
+    let (action, atomic) = (req.action, req.atomic);
+    let items = req.into_items();
+    if items.is_empty() { return Err(ApiError::bad_field("items", "must not be empty")); }
+    if items.len() > MAX_BATCH_ITEMS { return Err(ApiError::bad_field("items", &format!("must not have more than {} items", MAX_BATCH_ITEMS))); }
+    db::run(&data, move |conn| {
+        db::run_serializable(conn, |conn| {
+            // every student and course is looked up once for the whole batch
+            let emails = items.iter().map(|x| x.student_email.clone()).collect::<Vec<String>>();
+            let names = items.iter().map(|x| x.course_name.clone()).collect::<Vec<String>>();
+            let students = students_db_functions::get_students_by_emails(conn, &emails)?.into_iter().map(|x| (x.email.clone(), x)).collect::<HashMap<String, Student>>();
+            let courses = courses_db_functions::get_courses_by_names(conn, &names)?.into_iter().map(|x| (x.name.clone(), x)).collect::<HashMap<String, Course>>();
+            let mut report = BatchReport::default();
+            for (index, item) in items.into_iter().enumerate() {
+                // each item gets its own savepoint so a failed one leaves the others intact
+                let result = conn.transaction::<_, ApiError, _>(|conn| {
+                    let student = students.get(&item.student_email).ok_or_else(|| ApiError::bad_field("student_email", "student not found"))?;
+                    let course = courses.get(&item.course_name).ok_or_else(|| ApiError::bad_field("course_name", "course not found"))?;
+                    match action {
+                        BatchAction::Enroll => match sign_up(conn, student, course, item.offering_id)? {
+                            EnrollmentOutcome::Enrolled(_) => Ok(BatchItemStatus::Enrolled),
+                            EnrollmentOutcome::Waitlisted(_) => Ok(BatchItemStatus::Waitlisted),
+                        },
+                        BatchAction::Unenroll => drop_sign_up(conn, student, course, item.offering_id).map(|_| BatchItemStatus::Dropped),
+                    }
+                });
+                let (status, error) = match result {
+                    Ok(status) => (status, None),
+                    Err(error) if is_item_error(&error) => (BatchItemStatus::Failed, Some(BatchItemError::from(error))),
+                    Err(error) => return Err(error),
+                };
+                report.push(BatchItemResult { index, student_email: item.student_email, course_name: item.course_name, status, error });
+            }
+            if atomic && report.failed > 0 {
+                return Err(ApiError::BatchRejected(report.rolled_back()));
+            }
+            Ok(report)
+        })
+    }).await
+}
+
+// anything else means the transaction itself is broken and the whole batch has to fail
+fn is_item_error(error: &ApiError) -> bool {
+    use diesel::result::*;
+    !matches!(error, ApiError::DBError(e) if !matches!(e, Error::NotFound | Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _)))
+}
+
+fn sign_up(conn: &mut PgConnection, student: &Student, course: &Course, offering_id: Option<i32>) -> Result<EnrollmentOutcome, ApiError> {
+    let offering = resolve_offering(conn, course, offering_id)?;
+    let term = terms_db_functions::get_term(conn, offering.term_id)?;
+    if !term.registration_open { return Err(ApiError::RegistrationClosed(term.name)); }
+    Ok(students_courses_db_functions::create_student_course(conn, student.id, &offering)??)
+}
+
+// without an explicit offering the course must have exactly one section open for registration
+fn resolve_offering(conn: &mut PgConnection, course: &Course, offering_id: Option<i32>) -> Result<CourseOffering, ApiError> {
+    match offering_id {
+        Some(offering_id) => {
+            let offering = course_offerings_db_functions::get_course_offering(conn, offering_id)?;
+            if offering.course_id != course.id { return Err(ApiError::bad_field("offering_id", "offering does not belong to course")); }
+            Ok(offering)
+        }
+        None => {
+            let mut offerings = course_offerings_db_functions::get_open_offerings_for_course(conn, course.id)?;
+            match offerings.len() {
+                0 => Err(ApiError::bad_field("offering_id", "course has no offering open for registration")),
+                1 => Ok(offerings.remove(0)),
+                _ => Err(ApiError::bad_field("offering_id", "course has several offerings open for registration, pick one")),
+            }
+        }
+    }
+}
+
+// without an explicit offering this is the active enrollment in the course, or else the latest one
+fn find_enrollment(conn: &mut PgConnection, student: &Student, course: &Course, offering_id: Option<i32>) -> Result<Option<StudentCourse>, ApiError> {
+    match offering_id {
+        Some(offering_id) => Ok(students_courses_db_functions::get_student_course(conn, student.id, offering_id)?.filter(|x| x.course_id == course.id)),
+        None => Ok(students_courses_db_functions::get_current_student_course(conn, student.id, course.id)?),
+    }
+}
+
+// drops an active enrollment, or takes the student off the waitlist when they have no seat,
+// earlier attempts that were dropped or finished are left alone
+fn drop_sign_up(conn: &mut PgConnection, student: &Student, course: &Course, offering_id: Option<i32>) -> Result<(), ApiError> {
+    match find_enrollment(conn, student, course, offering_id)? {
+        Some(enrollment) if enrollment.status == EnrollmentStatus::Enrolled => { transition_status(conn, student, course, Some(enrollment.offering_id), EnrollmentStatus::Dropped)?; }
+        enrollment => {
+            if students_courses_db_functions::delete_waitlist_entry(conn, student.id, course.id, offering_id)? == 0 {
+                return Err(match enrollment {
+                    Some(enrollment) => ApiError::InvalidStatusTransition(enrollment.status, EnrollmentStatus::Dropped),
+                    None => ApiError::DBError(diesel::result::Error::NotFound),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn transition_status(conn: &mut PgConnection, student: &Student, course: &Course, offering_id: Option<i32>, status: EnrollmentStatus) -> Result<StudentCourse, ApiError> {
+    db::run_serializable(conn, |conn| {
+        let enrollment = find_enrollment(conn, student, course, offering_id)?
+            .ok_or(ApiError::DBError(diesel::result::Error::NotFound))?;
+        if !enrollment.status.can_transition_to(status) {
+            return Err(ApiError::InvalidStatusTransition(enrollment.status, status));
+        }
+        Ok(students_courses_db_functions::update_student_course_status(conn, student.id, enrollment.offering_id, status)?)
+    })
+}
+
 #[get("/course")]
-async fn fetch_students_in_course(data: web::Data<db::DbPool>, params: web::Query<GetStudentCourseByCourseRequest>) -> Result<impl Responder, ApiError> {
-    let mut connection = data.get().unwrap();
-    let students_vec = students_courses_db_functions::get_students_in_course(&mut connection, &params.course_name)?;
-    let students_list = format!("{:?}", students_vec.into_iter().map(|x| x.email).collect::<Vec<String>>());
-    Ok(HttpResponse::Ok().body(students_list))
+async fn fetch_students_in_course(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<GetStudentCourseByCourseRequest>) -> Result<impl Responder, ApiError> {
+    let (name, status) = (params.course_name.clone(), params.status);
+    let enrollments = db::run(&data, move |conn| {
+        let course = courses_db_functions::get_course(conn, &name)?;
+        policy::require_roster_access(conn, &principal, &course)?;
+        Ok(students_courses_db_functions::get_enrollments_for_course(conn, &course, status)?)
+    }).await?;
+    match params.fields {
+        Some(CourseStudentFields::Email) => Ok(HttpResponse::Ok().json(enrollments.into_iter().map(|(_, x)| x.email).collect::<Vec<String>>())),
+        None => Ok(HttpResponse::Ok().json(enrollments.into_iter().map(EnrolledStudentResponse::from).collect::<Vec<EnrolledStudentResponse>>())),
+    }
+}
+
+#[get("/waitlist")]
+async fn fetch_waitlist_for_course(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<GetWaitlistRequest>) -> Result<impl Responder, ApiError> {
+    let entries = db::run(&data, move |conn| {
+        let course = courses_db_functions::get_course(conn, &params.course_name)?;
+        policy::require_roster_access(conn, &principal, &course)?;
+        Ok(students_courses_db_functions::get_waitlist_for_course(conn, &course)?)
+    }).await?;
+    Ok(HttpResponse::Ok().json(entries.into_iter().enumerate().map(WaitlistEntryResponse::from).collect::<Vec<WaitlistEntryResponse>>()))
+}
+
+#[get("/export")]
+async fn export_student_courses(data: web::Data<db::DbPool>, principal: Principal, params: web::Query<ExportRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let (student_id, course_id) = (params.student_id, params.course_id);
+    db::run(&data, move |conn| {
+        if let Some(student_id) = student_id { students_db_functions::get_student_by_id(conn, student_id)?; }
+        if let Some(course_id) = course_id { courses_db_functions::get_course_by_id(conn, course_id)?; }
+        Ok(())
+    }).await?;
+    Ok(exports::stream_export(data.get_ref().clone(), params.format, "students_courses", &["student_id", "student_email", "course_id", "course_name", "offering_id", "status", "grade", "enrolled_at", "status_changed_at"], move |conn, after, limit| {
+        let rows = students_courses_db_functions::list_student_courses_for_export(conn, student_id, course_id, after, limit)?;
+        Ok(rows.into_iter().map(|x| ((x.0.student_id, x.0.offering_id), EnrollmentExportRow::from(x))).collect())
+    }))
 }
 
 #[cfg(test)]
 pub mod tests {
     use actix_web::{App, test, web};
-    use actix_web::web::resource;
+    use actix_web::http::StatusCode;
+    use chrono::NaiveDate;
 
+    use crate::api::auth::tests as auth_tests;
+    use crate::api::courses::handlers::courses_api_scope;
     use crate::api::courses::handlers::tests as courses_test;
     use crate::api::courses::types::CreateCourseRequest;
     use crate::api::students::handlers::tests as students_tests;
     use crate::api::students::types::CreateStudentRequest;
     use crate::api::students_courses::handlers::students_courses_api_scope;
-    use crate::api::students_courses::types::{CreateStudentCourseRequest, DeleteStudentCourseRequest};
+    use crate::api::students::handlers::student_api_scope;
+    use crate::api::students_courses::types::{CreateStudentCourseRequest, DeleteStudentCourseRequest, RecordGradesRequest, StudentGradeRequest, UpdateStudentCourseStatusRequest};
+    use crate::api::terms::handlers::tests as terms_tests;
+    use crate::api::terms::types::CreateTermRequest;
+    use crate::db::api_keys::models::Role;
+    use crate::db::instructors::db_functions as instructors_db_functions;
+    use crate::db::instructors::models::NewInstructor;
+    use crate::db::students::db_functions as students_db_functions;
+    use crate::db::students_courses::db_functions as students_courses_db_functions;
+    use crate::db::students_courses::models::{EnrollmentStatus, Grade};
+    use crate::db::course_offerings::db_functions as course_offerings_db_functions;
+    use crate::db::course_offerings::models::NewCourseOffering;
+    use crate::db::course_prerequisites::db_functions as course_prerequisites_db_functions;
+    use crate::db::courses::db_functions as courses_db_functions;
     use crate::db::initialize_db_pool;
+    use crate::db::terms::db_functions as terms_db_functions;
 
     #[actix_web::test]
     async fn test_create_and_delete_student_course_happy_path() {
@@ -73,19 +336,20 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(students_courses_api_scope())
         ).await;
 
         let req = test::TestRequest::post()
             .uri("/students-courses")
-            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone() })
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
             .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
 
         let req = test::TestRequest::delete()
             .uri("/students-courses")
-            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone() })
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
             .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
@@ -105,12 +369,13 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(students_courses_api_scope())
         ).await;
 
         let req = test::TestRequest::post()
             .uri("/students-courses")
-            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone() })
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
             .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_client_error());
@@ -119,6 +384,59 @@ pub mod tests {
         courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
     }
 
+    #[actix_web::test]
+    async fn test_create_student_course_resolves_offering() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("test_student_offering@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_course_offering"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let unscheduled = CreateCourseRequest { name: String::from("test_course_unscheduled"), ..Default::default() };
+        courses_db_functions::create_course(&mut pool.clone().get().unwrap(), unscheduled.clone().into()).unwrap();
+        let section = {
+            let mut conn = pool.get().unwrap();
+            let course_id = courses_db_functions::get_course(&mut conn, &course.name).unwrap().id;
+            let term_id = course_offerings_db_functions::tests::open_offering(&mut conn, course_id).unwrap().term_id;
+            course_offerings_db_functions::create_course_offering(&mut conn, NewCourseOffering { course_id, term_id, section: Some(String::from("002")), capacity: None }).unwrap()
+        };
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(students_courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: unscheduled.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["details"][0]["message"], "course has no offering open for registration");
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["details"][0]["field"], "offering_id");
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: Some(section.id) })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &unscheduled.name);
+    }
+
     #[actix_web::test]
     async fn test_create_student_course_no_course_found() {
         let pool = initialize_db_pool();
@@ -130,12 +448,13 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(students_courses_api_scope())
         ).await;
 
         let req = test::TestRequest::post()
             .uri("/students-courses")
-            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone() })
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
             .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_client_error());
@@ -156,26 +475,27 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(students_courses_api_scope())
         ).await;
 
         let req = test::TestRequest::post()
             .uri("/students-courses")
-            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone() })
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
             .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
 
         let req = test::TestRequest::post()
             .uri("/students-courses")
-            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone() })
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
             .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_client_error());
 
         let req = test::TestRequest::delete()
             .uri("/students-courses")
-            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone() })
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
             .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
@@ -195,12 +515,13 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(students_courses_api_scope())
         ).await;
 
         let req = test::TestRequest::post()
             .uri("/students-courses")
-            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone() })
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
             .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
@@ -208,12 +529,19 @@ pub mod tests {
         let req = test::TestRequest::get()
             .uri(&format!("/students-courses/student?student_email={}", &request.email))
             .to_request();
-        let resp = test::call_service(&app, req).await;
-        assert!(resp.status().is_success());
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp[0]["name"], course.name.as_str());
+        assert_eq!(resp[0]["enrollment"]["course_id"], resp[0]["id"]);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/student?student_email={}&fields=name", &request.email))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, serde_json::json!([course.name]));
 
         let req = test::TestRequest::delete()
             .uri("/students-courses")
-            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone() })
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
             .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
@@ -233,6 +561,7 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(students_courses_api_scope())
         ).await;
 
@@ -257,12 +586,13 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(students_courses_api_scope())
         ).await;
 
         let req = test::TestRequest::post()
             .uri("/students-courses")
-            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone() })
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
             .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
@@ -270,12 +600,19 @@ pub mod tests {
         let req = test::TestRequest::get()
             .uri(&format!("/students-courses/course?course_name={}", &course.name))
             .to_request();
-        let resp = test::call_service(&app, req).await;
-        assert!(resp.status().is_success());
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp[0]["email"], request.email.as_str());
+        assert_eq!(resp[0]["enrollment"]["student_id"], resp[0]["id"]);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/course?course_name={}&fields=email", &course.name))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, serde_json::json!([request.email]));
 
         let req = test::TestRequest::delete()
             .uri("/students-courses")
-            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone() })
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
             .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
@@ -295,16 +632,675 @@ pub mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(students_courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/course?course_name={}", &course.name))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+    }
+
+    #[actix_web::test]
+    async fn test_create_student_course_waitlisted_and_promoted() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("test_student_nine@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let other = CreateStudentRequest { email: String::from("test_student_ten@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), other.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_course_nine"), capacity: Some(1), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(students_courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: other.email.clone(), course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/waitlist?course_name={}", &course.name))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp[0]["student_email"], other.email.as_str());
+        assert_eq!(resp[0]["position"], 1);
+
+        let req = test::TestRequest::delete()
+            .uri("/students-courses")
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/course?course_name={}&fields=email", &course.name))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, serde_json::json!([other.email]));
+
+        let req = test::TestRequest::delete()
+            .uri("/students-courses")
+            .set_json(DeleteStudentCourseRequest { student_email: other.email.clone(), course_name: course.name.clone(), offering_id: None })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &other.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+    }
+
+    #[actix_web::test]
+    async fn test_create_student_course_missing_prerequisites() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("test_student_eleven@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_course_eleven"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let prerequisite = CreateCourseRequest { name: String::from("test_course_twelve"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), prerequisite.clone().into());
+        let course_id = courses_db_functions::get_course(&mut pool.clone().get().unwrap(), &course.name).unwrap().id;
+        let prerequisite_id = courses_db_functions::get_course(&mut pool.clone().get().unwrap(), &prerequisite.name).unwrap().id;
+        course_prerequisites_db_functions::create_course_prerequisite(&mut pool.clone().get().unwrap(), course_id, prerequisite_id).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(students_courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["details"], serde_json::json!([prerequisite.name]));
+
+        course_prerequisites_db_functions::delete_course_prerequisite(&mut pool.clone().get().unwrap(), course_id, prerequisite_id).unwrap();
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &prerequisite.name);
+    }
+
+    #[actix_web::test]
+    async fn test_update_student_course_status_transitions() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("test_student_twelve@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_course_thirteen"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(students_courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::patch()
+            .uri("/students-courses")
+            .set_json(UpdateStudentCourseStatusRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None, status: EnrollmentStatus::Completed })
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["status"], "completed");
+
+        let req = test::TestRequest::delete()
+            .uri("/students-courses")
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["details"], serde_json::json!({ "from": "completed", "to": "dropped" }));
+
+        let req = test::TestRequest::delete()
+            .uri("/students-courses/hard")
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/student?student_email={}&status=completed", &request.email))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, serde_json::json!([]));
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_student_course_is_soft_drop() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("test_student_thirteen@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_course_fourteen"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(students_courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::delete()
+            .uri("/students-courses")
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/student?student_email={}&status=dropped", &request.email))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp[0]["enrollment"]["status"], "dropped");
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_student_course_waitlisted_after_earlier_drop() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("test_student_twenty_two@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let other = CreateStudentRequest { email: String::from("test_student_twenty_three@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), other.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_course_twenty_two"), capacity: Some(1), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let term = CreateTermRequest { name: String::from("test_term_past_one"), starts_on: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2025, 6, 30).unwrap() };
+        {
+            let mut conn = pool.get().unwrap();
+            let student_id = students_db_functions::get_student(&mut conn, &request.email).unwrap().id;
+            let course_id = courses_db_functions::get_course(&mut conn, &course.name).unwrap().id;
+            let term_id = terms_db_functions::create_term(&mut conn, term.clone().into()).unwrap().id;
+            let past = course_offerings_db_functions::create_course_offering(&mut conn, NewCourseOffering { course_id, term_id, section: None, capacity: Some(1) }).unwrap();
+            students_courses_db_functions::create_student_course(&mut conn, student_id, &past).unwrap().unwrap();
+            students_courses_db_functions::update_student_course_status(&mut conn, student_id, past.id, EnrollmentStatus::Dropped).unwrap();
+        }
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(students_courses_api_scope())
+        ).await;
+
+        for (email, status) in [(&other.email, StatusCode::OK), (&request.email, StatusCode::ACCEPTED)] {
+            let req = test::TestRequest::post()
+                .uri("/students-courses")
+                .set_json(CreateStudentCourseRequest { student_email: email.clone(), course_name: course.name.clone(), ..Default::default() })
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status);
+        }
+
+        let req = test::TestRequest::delete()
+            .uri("/students-courses/hard")
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::delete()
+            .uri("/students-courses")
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/waitlist?course_name={}", &course.name))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, serde_json::json!([]));
+
+        let req = test::TestRequest::delete()
+            .uri("/students-courses/hard")
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/student?student_email={}&status=dropped", &request.email))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, serde_json::json!([]));
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &other.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+        terms_tests::cleanup(&mut pool.clone().get().unwrap(), &term.name);
+    }
+
+    #[actix_web::test]
+    async fn test_record_grades_and_transcript() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("test_student_fourteen@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let other = CreateStudentRequest { email: String::from("test_student_fifteen@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), other.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_course_fifteen"), credits: Some(4), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let student_id = students_db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).unwrap().id;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
                 .service(students_courses_api_scope())
+                .service(student_api_scope())
         ).await;
 
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::put()
+            .uri("/students-courses/grades")
+            .set_json(RecordGradesRequest {
+                course_name: course.name.clone(),
+                offering_id: None,
+                grades: vec![
+                    StudentGradeRequest { student_email: request.email.clone(), grade: Grade::AMinus },
+                    StudentGradeRequest { student_email: other.email.clone(), grade: Grade::B },
+                ],
+            })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["details"][0]["field"], "grades[1].student_email");
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students/{}/transcript", student_id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["courses"], serde_json::json!([]));
+
+        let req = test::TestRequest::put()
+            .uri("/students-courses/grades")
+            .set_json(RecordGradesRequest {
+                course_name: course.name.clone(),
+                offering_id: None,
+                grades: vec![StudentGradeRequest { student_email: request.email.clone(), grade: Grade::AMinus }],
+            })
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp[0]["status"], "completed");
+        assert_eq!(resp[0]["grade"], "A-");
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students/{}/transcript", student_id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["courses"][0]["name"], course.name.as_str());
+        assert_eq!(resp["earned_credits"], 4);
+        assert_eq!(resp["gpa"], 3.7);
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &other.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+    }
+
+    #[actix_web::test]
+    async fn test_create_student_course_schedule_conflict() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("test_student_sixteen@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_course_sixteen"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let clashing = CreateCourseRequest { name: String::from("test_course_seventeen"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), clashing.clone().into());
+        let student_id = students_db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).unwrap().id;
+        let course_id = courses_db_functions::get_course(&mut pool.clone().get().unwrap(), &course.name).unwrap().id;
+        let clashing_id = courses_db_functions::get_course(&mut pool.clone().get().unwrap(), &clashing.name).unwrap().id;
+        let offering_id = course_offerings_db_functions::tests::open_offering(&mut pool.clone().get().unwrap(), course_id).unwrap().id;
+        let clashing_offering_id = course_offerings_db_functions::tests::open_offering(&mut pool.clone().get().unwrap(), clashing_id).unwrap().id;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(students_courses_api_scope())
+                .service(student_api_scope())
+                .service(courses_api_scope())
+        ).await;
+
+        for (id, offering, start_time, end_time) in [(course_id, offering_id, "09:00", "10:30"), (clashing_id, clashing_offering_id, "10:00", "11:00")] {
+            let req = test::TestRequest::post()
+                .uri(&format!("/courses/{}/offerings/{}/meetings", id, offering))
+                .set_json(serde_json::json!({ "day_of_week": "monday", "start_time": start_time, "end_time": end_time, "room": "B12" }))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert!(resp.status().is_success());
+        }
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: clashing.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["code"], "schedule_conflict");
+        assert_eq!(body["details"][0]["course_name"], course.name.as_str());
+        assert_eq!(body["details"][0]["slot"]["start_time"], "09:00");
+        assert_eq!(body["details"][0]["conflicts_with"]["start_time"], "10:00");
+        let rejected = students_courses_db_functions::get_student_course(&mut pool.clone().get().unwrap(), student_id, clashing_offering_id).unwrap();
+        assert!(rejected.is_none());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students/{}/schedule", student_id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["days"][0]["day_of_week"], "monday");
+        assert_eq!(resp["days"][0]["slots"][0]["course_name"], course.name.as_str());
+        assert_eq!(resp["days"][0]["slots"][0]["room"], "B12");
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &clashing.name);
+    }
+
+    #[actix_web::test]
+    async fn test_export_student_courses() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("test_student_seventeen@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_course_eighteen"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let course_id = courses_db_functions::get_course(&mut pool.clone().get().unwrap(), &course.name).unwrap().id;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(student_api_scope())
+                .service(courses_api_scope())
+                .service(students_courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/export?course_id={}&format=ndjson", course_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.headers().get("content-type").unwrap(), "application/x-ndjson");
+        let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+        let rows = body.lines().map(|x| serde_json::from_str::<serde_json::Value>(x).unwrap()).collect::<Vec<_>>();
+        assert_eq!(1, rows.len());
+        assert_eq!(rows[0]["student_email"], request.email.as_str());
+        assert_eq!(rows[0]["course_name"], course.name.as_str());
+        assert_eq!(rows[0]["status"], "enrolled");
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students/export?course_id={}", course_id))
+            .to_request();
+        let body = String::from_utf8(test::call_and_read_body(&app, req).await.to_vec()).unwrap();
+        let lines = body.lines().collect::<Vec<&str>>();
+        assert_eq!("id,email,phone_number,created_at,updated_at", lines[0]);
+        assert_eq!(2, lines.len());
+        assert!(lines[1].contains(&request.email));
+
+        let student_id = students_db_functions::get_student(&mut pool.clone().get().unwrap(), &request.email).unwrap().id;
+        let req = test::TestRequest::get()
+            .uri(&format!("/courses/export?student_id={}&format=ndjson", student_id))
+            .to_request();
+        let body = String::from_utf8(test::call_and_read_body(&app, req).await.to_vec()).unwrap();
+        assert_eq!(vec![course.name.as_str()], body.lines().map(|x| serde_json::from_str::<serde_json::Value>(x).unwrap()["name"].as_str().unwrap().to_string()).collect::<Vec<String>>());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students/export?student_id={}", student_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/export?course_id={}", course_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn test_batch_enroll_and_unenroll() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("test_student_eighteen@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let other = CreateStudentRequest { email: String::from("test_student_nineteen@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), other.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_course_nineteen"), capacity: Some(1), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(students_courses_api_scope())
+        ).await;
+
+        let missing = "test_student_missing@gmail.com";
+        let req = test::TestRequest::post()
+            .uri("/students-courses/batch")
+            .set_json(serde_json::json!({ "action": "enroll", "atomic": true, "course_name": course.name, "student_emails": [request.email, missing] }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["code"], "batch_rejected");
+        assert_eq!(body["details"]["items"][0]["status"], "skipped");
+        assert_eq!(body["details"]["items"][1]["error"]["details"][0]["field"], "student_email");
+
         let req = test::TestRequest::get()
             .uri(&format!("/students-courses/course?course_name={}", &course.name))
             .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, serde_json::json!([]));
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses/batch")
+            .set_json(serde_json::json!({ "action": "enroll", "course_name": course.name, "student_emails": [request.email, other.email, missing] }))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["succeeded"], 2);
+        assert_eq!(resp["failed"], 1);
+        assert_eq!(resp["items"].as_array().unwrap().iter().map(|x| x["status"].as_str().unwrap()).collect::<Vec<&str>>(), vec!["enrolled", "waitlisted", "failed"]);
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses/batch")
+            .set_json(serde_json::json!({ "action": "unenroll", "items": [
+                { "student_email": request.email, "course_name": course.name },
+                { "student_email": other.email, "course_name": "test_course_missing" },
+            ] }))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["items"][0]["status"], "dropped");
+        assert_eq!(resp["items"][1]["error"]["details"][0]["field"], "course_name");
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/course?course_name={}&fields=email", &course.name))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, serde_json::json!([other.email]));
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses/batch")
+            .set_json(serde_json::json!({ "action": "enroll", "items": [] }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &other.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+    }
+
+    #[actix_web::test]
+    async fn test_role_policies() {
+        let pool = initialize_db_pool();
+        let request = CreateStudentRequest { email: String::from("test_student_twenty@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let other = CreateStudentRequest { email: String::from("test_student_twenty_one@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), other.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_course_twenty"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let other_course = CreateCourseRequest { name: String::from("test_course_twenty_one"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), other_course.clone().into());
+        let instructor_email = "test_instructor_twenty@gmail.com";
+        {
+            let mut conn = pool.get().unwrap();
+            instructors_db_functions::delete_instructor(&mut conn, instructor_email).unwrap();
+            let instructor = instructors_db_functions::create_instructor(&mut conn, NewInstructor { name: String::from("Test Instructor"), email: String::from(instructor_email), ..Default::default() }).unwrap();
+            let assigned = courses_db_functions::get_course(&mut conn, &course.name).unwrap();
+            instructors_db_functions::assign_instructor_to_course(&mut conn, assigned.id, instructor.id).unwrap();
+        }
+
+        let student_email = request.email.clone();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(move |req, srv| auth_tests::as_principal(req, srv, &student_email, Role::Student))
+                .service(student_api_scope())
+                .service(students_courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: other.email.clone(), course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["code"], "forbidden");
+
+        let req = test::TestRequest::get().uri(&format!("/students?email={}", &request.email)).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        for uri in [format!("/students?email={}", &other.email), format!("/students-courses/student?student_email={}", &other.email), format!("/students-courses/course?course_name={}", &course.name)] {
+            let req = test::TestRequest::get().uri(&uri).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN, "{}", uri);
+        }
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(move |req, srv| auth_tests::as_principal(req, srv, instructor_email, Role::Instructor))
+                .service(students_courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/course?course_name={}&fields=email", &course.name))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp, serde_json::json!([request.email]));
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/course?course_name={}", &other_course.name))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: other.email.clone(), course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(|req, srv| auth_tests::as_principal(req, srv, "registrar@example.com", Role::Registrar))
+                .service(students_courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::delete()
+            .uri("/students-courses/hard")
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::delete()
+            .uri("/students-courses")
+            .set_json(DeleteStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: None })
+            .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
 
         students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &other.email);
         courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &other_course.name);
+        instructors_db_functions::delete_instructor(&mut pool.get().unwrap(), instructor_email).unwrap();
     }
-}
\ No newline at end of file
+}
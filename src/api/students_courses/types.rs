@@ -1,24 +1,325 @@
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use actix_web::body::BoxBody;
+use actix_web::http::header::ContentType;
+use chrono::{Local, TimeZone};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::api::courses::types::CourseResponse;
+use crate::api::errors::ApiError;
+use crate::api::students::types::StudentResponse;
+use crate::db::courses::models::Course;
+use crate::db::students::models::Student;
+use crate::db::students_courses::models::{EnrollmentStatus, Grade, StudentCourse, WaitlistEntry};
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StudentCourseFields {
+    Name,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CourseStudentFields {
+    Email,
+}
 
 #[derive(Deserialize)]
 pub struct GetStudentCourseByStudentRequest {
-    pub student_email: String
+    pub student_email: String,
+    pub fields: Option<StudentCourseFields>,
+    pub status: Option<EnrollmentStatus>,
 }
 
 #[derive(Deserialize)]
 pub struct GetStudentCourseByCourseRequest {
-    pub course_name: String
+    pub course_name: String,
+    pub fields: Option<CourseStudentFields>,
+    pub status: Option<EnrollmentStatus>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Deserialize)]
+pub struct GetWaitlistRequest {
+    pub course_name: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
 pub struct CreateStudentCourseRequest {
     pub student_email: String,
     pub course_name: String,
+    pub offering_id: Option<i32>,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct DeleteStudentCourseRequest {
     pub student_email: String,
     pub course_name: String,
+    pub offering_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UpdateStudentCourseStatusRequest {
+    pub student_email: String,
+    pub course_name: String,
+    pub offering_id: Option<i32>,
+    pub status: EnrollmentStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StudentGradeRequest {
+    pub student_email: String,
+    pub grade: Grade,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RecordGradesRequest {
+    pub course_name: String,
+    pub offering_id: Option<i32>,
+    pub grades: Vec<StudentGradeRequest>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchAction {
+    Enroll,
+    Unenroll,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct BatchStudentCourseItem {
+    pub student_email: String,
+    pub course_name: String,
+    pub offering_id: Option<i32>,
+}
+
+// either explicit pairs or one course with a list of students
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum BatchTargets {
+    Pairs {
+        items: Vec<BatchStudentCourseItem>,
+    },
+    Course {
+        course_name: String,
+        offering_id: Option<i32>,
+        student_emails: Vec<String>,
+    },
+}
+
+#[derive(Deserialize)]
+pub struct BatchStudentCourseRequest {
+    pub action: BatchAction,
+    #[serde(default)]
+    pub atomic: bool,
+    #[serde(flatten)]
+    pub targets: BatchTargets,
+}
+
+impl BatchStudentCourseRequest {
+    pub fn into_items(self) -> Vec<BatchStudentCourseItem> {
+        match self.targets {
+            BatchTargets::Pairs { items } => items,
+            BatchTargets::Course { course_name, offering_id, student_emails } => student_emails.into_iter()
+                .map(|student_email| BatchStudentCourseItem { student_email, course_name: course_name.clone(), offering_id })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchItemStatus {
+    Enrolled,
+    Waitlisted,
+    Dropped,
+    Failed,
+    Skipped,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BatchItemError {
+    pub code: &'static str,
+    pub message: String,
+    pub details: Value,
+}
+
+impl From<ApiError> for BatchItemError {
+    fn from(error: ApiError) -> Self {
+        let body = error.to_body();
+        BatchItemError { code: body.code, message: body.message, details: body.details }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct BatchItemResult {
+    pub index: usize,
+    pub student_email: String,
+    pub course_name: String,
+    pub status: BatchItemStatus,
+    pub error: Option<BatchItemError>,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct BatchReport {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub items: Vec<BatchItemResult>,
+}
+
+impl BatchReport {
+    pub fn push(&mut self, item: BatchItemResult) {
+        match item.status {
+            BatchItemStatus::Failed => self.failed += 1,
+            BatchItemStatus::Skipped => self.skipped += 1,
+            _ => self.succeeded += 1,
+        }
+        self.items.push(item);
+    }
+
+    // nothing was written, so items that went through are reported as skipped
+    pub fn rolled_back(self) -> Self {
+        let mut report = BatchReport::default();
+        for item in self.items {
+            match item.status {
+                BatchItemStatus::Failed => report.push(item),
+                _ => report.push(BatchItemResult { status: BatchItemStatus::Skipped, ..item }),
+            }
+        }
+        report
+    }
+}
+
+impl Responder for BatchReport {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = serde_json::to_string(&self).unwrap();
+
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+}
+
+#[derive(Serialize)]
+pub struct EnrollmentResponse {
+    student_id: i32,
+    course_id: i32,
+    offering_id: i32,
+    status: EnrollmentStatus,
+    grade: Option<Grade>,
+    enrolled_at: String,
+    status_changed_at: String,
+}
+
+impl From<StudentCourse> for EnrollmentResponse {
+    fn from(student_course: StudentCourse) -> Self {
+        EnrollmentResponse {
+            student_id: student_course.student_id,
+            course_id: student_course.course_id,
+            offering_id: student_course.offering_id,
+            status: student_course.status,
+            grade: student_course.grade,
+            enrolled_at: Local.from_utc_datetime(&student_course.enrolled_at).format("%Y-%m-%d %H:%M:%S").to_string(),
+            status_changed_at: Local.from_utc_datetime(&student_course.status_changed_at).format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+impl Responder for EnrollmentResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = serde_json::to_string(&self).unwrap();
+
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+}
+
+#[derive(Serialize)]
+pub struct EnrolledCourseResponse {
+    #[serde(flatten)]
+    course: CourseResponse,
+    enrollment: EnrollmentResponse,
+}
+
+impl From<(StudentCourse, Course)> for EnrolledCourseResponse {
+    fn from((student_course, course): (StudentCourse, Course)) -> Self {
+        EnrolledCourseResponse {
+            course: CourseResponse::from(course),
+            enrollment: EnrollmentResponse::from(student_course),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct EnrolledStudentResponse {
+    #[serde(flatten)]
+    student: StudentResponse,
+    enrollment: EnrollmentResponse,
+}
+
+impl From<(StudentCourse, Student)> for EnrolledStudentResponse {
+    fn from((student_course, student): (StudentCourse, Student)) -> Self {
+        EnrolledStudentResponse {
+            student: StudentResponse::from(student),
+            enrollment: EnrollmentResponse::from(student_course),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct WaitlistEntryResponse {
+    position: usize,
+    student_id: i32,
+    student_email: String,
+    course_id: i32,
+    offering_id: i32,
+    created_at: String,
 }
 
+impl From<(usize, (WaitlistEntry, Student))> for WaitlistEntryResponse {
+    fn from((index, (entry, student)): (usize, (WaitlistEntry, Student))) -> Self {
+        WaitlistEntryResponse {
+            position: index + 1,
+            student_id: entry.student_id,
+            student_email: student.email,
+            course_id: entry.course_id,
+            offering_id: entry.offering_id,
+            created_at: Local.from_utc_datetime(&entry.created_at).format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+// csv rows cannot hold nested values, so the export keeps every column at the top level
+#[derive(Serialize)]
+pub struct EnrollmentExportRow {
+    student_id: i32,
+    student_email: String,
+    course_id: i32,
+    course_name: String,
+    offering_id: i32,
+    status: EnrollmentStatus,
+    grade: Option<Grade>,
+    enrolled_at: String,
+    status_changed_at: String,
+}
+
+impl From<(StudentCourse, Student, Course)> for EnrollmentExportRow {
+    fn from((student_course, student, course): (StudentCourse, Student, Course)) -> Self {
+        EnrollmentExportRow {
+            student_id: student_course.student_id,
+            student_email: student.email,
+            course_id: student_course.course_id,
+            course_name: course.name,
+            offering_id: student_course.offering_id,
+            status: student_course.status,
+            grade: student_course.grade,
+            enrolled_at: Local.from_utc_datetime(&student_course.enrolled_at).format("%Y-%m-%d %H:%M:%S").to_string(),
+            status_changed_at: Local.from_utc_datetime(&student_course.status_changed_at).format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
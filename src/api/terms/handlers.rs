@@ -0,0 +1,349 @@
+use actix_web::{get, HttpResponse, post, Responder, Scope, web};
+
+use crate::api::auth::Principal;
+use crate::api::errors::ApiError;
+use crate::api::policy;
+use crate::api::terms::types::{CourseOfferingResponse, CreateCourseOfferingRequest, CreateTermRequest, TermResponse};
+use crate::db;
+use crate::db::course_offerings::db_functions as course_offerings_db_functions;
+use crate::db::course_offerings::models::NewCourseOffering;
+use crate::db::courses::db_functions as courses_db_functions;
+use crate::db::terms::db_functions;
+use crate::db::terms::models::NewTerm;
+
+pub fn terms_api_scope() -> Scope {
+    web::scope("/terms")
+        .service(create_term)
+        .service(get_term)
+        .service(open_registration)
+        .service(close_registration)
+        .service(create_course_offering)
+        .service(list_course_offerings)
+}
+
+#[post("")]
+async fn create_term(data: web::Data<db::DbPool>, principal: Principal, req: web::Json<CreateTermRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    if req.ends_on < req.starts_on { return Err(ApiError::bad_field("ends_on", "must not be before starts_on")); }
+    let term = db::run(&data, move |conn| Ok(db_functions::create_term(conn, NewTerm::from(req.0))?)).await?;
+    Ok(TermResponse::from(term))
+}
+
+#[get("/{id}")]
+async fn get_term(data: web::Data<db::DbPool>, _principal: Principal, path: web::Path<i32>) -> Result<impl Responder, ApiError> {
+    let term = db::run(&data, move |conn| Ok(db_functions::get_term(conn, path.into_inner())?)).await?;
+    Ok(TermResponse::from(term))
+}
+
+#[post("/{id}/registration/open")]
+async fn open_registration(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let term = db::run(&data, move |conn| Ok(db_functions::set_registration_open(conn, path.into_inner(), true)?)).await?;
+    Ok(TermResponse::from(term))
+}
+
+#[post("/{id}/registration/close")]
+async fn close_registration(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    let term = db::run(&data, move |conn| Ok(db_functions::set_registration_open(conn, path.into_inner(), false)?)).await?;
+    Ok(TermResponse::from(term))
+}
+
+#[post("/{id}/offerings")]
+async fn create_course_offering(data: web::Data<db::DbPool>, principal: Principal, path: web::Path<i32>, req: web::Json<CreateCourseOfferingRequest>) -> Result<impl Responder, ApiError> {
+    policy::require_role(&principal, policy::STAFF)?;
+    if req.capacity.is_some_and(|capacity| capacity < 0) { return Err(ApiError::bad_field("capacity", "must not be negative")); }
+    if req.section.as_ref().is_some_and(|section| section.trim().is_empty()) { return Err(ApiError::bad_field("section", "must not be empty")); }
+    let offering = db::run(&data, move |conn| {
+        let term = db_functions::get_term(conn, path.into_inner())?;
+        let course = courses_db_functions::get_course(conn, &req.course_name)?;
+        let new_offering = NewCourseOffering { course_id: course.id, term_id: term.id, section: req.section.clone(), capacity: req.capacity.or(course.capacity) };
+        Ok((course_offerings_db_functions::create_course_offering(conn, new_offering)?, course))
+    }).await?;
+    Ok(CourseOfferingResponse::from(offering))
+}
+
+#[get("/{id}/offerings")]
+async fn list_course_offerings(data: web::Data<db::DbPool>, _principal: Principal, path: web::Path<i32>) -> Result<impl Responder, ApiError> {
+    let offerings = db::run(&data, move |conn| {
+        let term = db_functions::get_term(conn, path.into_inner())?;
+        Ok(course_offerings_db_functions::get_offerings_for_term(conn, &term)?)
+    }).await?;
+    Ok(HttpResponse::Ok().json(offerings.into_iter().map(CourseOfferingResponse::from).collect::<Vec<CourseOfferingResponse>>()))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use actix_web::{App, test, web};
+    use actix_web::http::StatusCode;
+    use chrono::NaiveDate;
+    use diesel::prelude::*;
+
+    use crate::api::auth::tests as auth_tests;
+    use crate::api::courses::handlers::tests as courses_test;
+    use crate::api::courses::types::CreateCourseRequest;
+    use crate::api::students::handlers::tests as students_tests;
+    use crate::api::students::types::CreateStudentRequest;
+    use crate::api::students_courses::handlers::students_courses_api_scope;
+    use crate::api::students_courses::types::CreateStudentCourseRequest;
+    use crate::api::terms::handlers::terms_api_scope;
+    use crate::api::terms::types::{CreateCourseOfferingRequest, CreateTermRequest};
+    use crate::db::api_keys::models::Role;
+    use crate::db::initialize_db_pool;
+    use crate::db::terms::db_functions as terms_db_functions;
+    use crate::schema::terms;
+
+    #[actix_web::test]
+    async fn test_create_term_and_offerings() {
+        let pool = initialize_db_pool();
+        let term = CreateTermRequest { name: String::from("test_term_one"), starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 12, 18).unwrap() };
+        let course = CreateCourseRequest { name: String::from("test_term_course_one"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(terms_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/terms")
+            .set_json(term.clone())
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["starts_on"], "2026-09-01");
+        assert_eq!(resp["registration_open"], false);
+        let term_id = resp["id"].as_i64().unwrap();
+
+        for section in ["001", "002"] {
+            let req = test::TestRequest::post()
+                .uri(&format!("/terms/{}/offerings", term_id))
+                .set_json(CreateCourseOfferingRequest { course_name: course.name.clone(), section: Some(String::from(section)), capacity: Some(30) })
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert!(resp.status().is_success());
+        }
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/terms/{}/offerings", term_id))
+            .set_json(CreateCourseOfferingRequest { course_name: course.name.clone(), section: Some(String::from("001")), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/terms/{}/offerings", term_id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp.as_array().unwrap().len(), 2);
+        assert_eq!(resp[1]["section"], "002");
+        assert_eq!(resp[1]["course"]["name"], course.name.as_str());
+
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+        cleanup(&mut pool.clone().get().unwrap(), &term.name);
+    }
+
+    #[actix_web::test]
+    async fn test_create_term_ends_before_start() {
+        let pool = initialize_db_pool();
+        let term = CreateTermRequest { name: String::from("test_term_two"), starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 8, 1).unwrap() };
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(terms_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/terms")
+            .set_json(term.clone())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        cleanup(&mut pool.clone().get().unwrap(), &term.name);
+    }
+
+    #[actix_web::test]
+    async fn test_enroll_in_offering_requires_open_registration() {
+        let pool = initialize_db_pool();
+        let term = CreateTermRequest { name: String::from("test_term_three"), starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 12, 18).unwrap() };
+        let request = CreateStudentRequest { email: String::from("test_term_student_one@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), request.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_term_course_two"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(terms_api_scope())
+                .service(students_courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/terms")
+            .set_json(term.clone())
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let term_id = resp["id"].as_i64().unwrap();
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/terms/{}/offerings", term_id))
+            .set_json(CreateCourseOfferingRequest { course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["section"], "001");
+        let offering_id = resp["id"].as_i64().unwrap() as i32;
+
+        let enroll = CreateStudentCourseRequest { student_email: request.email.clone(), course_name: course.name.clone(), offering_id: Some(offering_id) };
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(&enroll)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["code"], "registration_closed");
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/terms/{}/registration/open", term_id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["registration_open"], true);
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(&enroll)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/students-courses/student?student_email={}", &request.email))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp[0]["enrollment"]["offering_id"], offering_id);
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/terms/{}/registration/close", term_id))
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["registration_open"], false);
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &request.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+        cleanup(&mut pool.clone().get().unwrap(), &term.name);
+    }
+
+    #[actix_web::test]
+    async fn test_offering_defaults_to_course_capacity() {
+        let pool = initialize_db_pool();
+        let term = CreateTermRequest { name: String::from("test_term_five"), starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 12, 18).unwrap() };
+        let first = CreateStudentRequest { email: String::from("test_term_student_two@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), first.clone().into());
+        let second = CreateStudentRequest { email: String::from("test_term_student_three@gmail.com"), ..Default::default() };
+        students_tests::setup_existing_student(true, &mut pool.clone().get().unwrap(), second.clone().into());
+        let course = CreateCourseRequest { name: String::from("test_term_course_four"), capacity: Some(1), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(auth_tests::as_admin)
+                .service(terms_api_scope())
+                .service(students_courses_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/terms")
+            .set_json(term.clone())
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let term_id = resp["id"].as_i64().unwrap();
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/terms/{}/offerings", term_id))
+            .set_json(CreateCourseOfferingRequest { course_name: course.name.clone(), ..Default::default() })
+            .to_request();
+        let resp: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(resp["capacity"], 1);
+        let offering_id = resp["id"].as_i64().unwrap() as i32;
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/terms/{}/registration/open", term_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: first.email.clone(), course_name: course.name.clone(), offering_id: Some(offering_id) })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let req = test::TestRequest::post()
+            .uri("/students-courses")
+            .set_json(CreateStudentCourseRequest { student_email: second.email.clone(), course_name: course.name.clone(), offering_id: Some(offering_id) })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &first.email);
+        students_tests::cleanup(&mut pool.clone().get().unwrap(), &second.email);
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+        cleanup(&mut pool.clone().get().unwrap(), &term.name);
+    }
+
+    #[actix_web::test]
+    async fn test_term_writes_require_staff() {
+        let pool = initialize_db_pool();
+        let term = CreateTermRequest { name: String::from("test_term_four"), starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 12, 18).unwrap() };
+        let course = CreateCourseRequest { name: String::from("test_term_course_three"), ..Default::default() };
+        courses_test::setup_existing_course(true, &mut pool.clone().get().unwrap(), course.clone().into());
+        let term_id = terms_db_functions::create_term(&mut pool.clone().get().unwrap(), term.clone().into()).expect("setup failed").id;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool.clone()))
+                .wrap_fn(|req, srv| auth_tests::as_principal(req, srv, "instructor@example.com", Role::Instructor))
+                .service(terms_api_scope())
+        ).await;
+
+        let req = test::TestRequest::post()
+            .uri("/terms")
+            .set_json(term.clone())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        for uri in [format!("/terms/{}/registration/open", term_id), format!("/terms/{}/registration/close", term_id)] {
+            let req = test::TestRequest::post().uri(&uri).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN, "{}", uri);
+        }
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/terms/{}/offerings", term_id))
+            .set_json(CreateCourseOfferingRequest { course_name: course.name.clone(), section: Some(String::from("002")), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        for uri in [format!("/terms/{}", term_id), format!("/terms/{}/offerings", term_id)] {
+            let req = test::TestRequest::get().uri(&uri).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert!(resp.status().is_success(), "{}", uri);
+        }
+
+        courses_test::cleanup(&mut pool.clone().get().unwrap(), &course.name);
+        cleanup(&mut pool.clone().get().unwrap(), &term.name);
+    }
+
+    pub fn cleanup(conn: &mut PgConnection, name: &str) {
+        diesel::delete(terms::table.filter(terms::name.eq(name))).execute(conn).expect("cleanup failed");
+    }
+}
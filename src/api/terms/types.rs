@@ -0,0 +1,90 @@
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use actix_web::body::BoxBody;
+use actix_web::http::header::ContentType;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::api::courses::types::CourseResponse;
+use crate::db::course_offerings::models::CourseOffering;
+use crate::db::courses::models::Course;
+use crate::db::terms::models::Term;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CreateTermRequest {
+    pub name: String,
+    pub starts_on: NaiveDate,
+    pub ends_on: NaiveDate,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct CreateCourseOfferingRequest {
+    pub course_name: String,
+    pub section: Option<String>,
+    pub capacity: Option<i32>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TermResponse {
+    id: i32,
+    name: String,
+    starts_on: NaiveDate,
+    ends_on: NaiveDate,
+    registration_open: bool,
+}
+
+impl From<Term> for TermResponse {
+    fn from(term: Term) -> Self {
+        TermResponse {
+            id: term.id,
+            name: term.name,
+            starts_on: term.starts_on,
+            ends_on: term.ends_on,
+            registration_open: term.registration_open,
+        }
+    }
+}
+
+impl Responder for TermResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = serde_json::to_string(&self).unwrap();
+
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct CourseOfferingResponse {
+    id: i32,
+    term_id: i32,
+    section: String,
+    capacity: Option<i32>,
+    course: CourseResponse,
+}
+
+impl From<(CourseOffering, Course)> for CourseOfferingResponse {
+    fn from((offering, course): (CourseOffering, Course)) -> Self {
+        CourseOfferingResponse {
+            id: offering.id,
+            term_id: offering.term_id,
+            section: offering.section,
+            capacity: offering.capacity,
+            course: CourseResponse::from(course),
+        }
+    }
+}
+
+impl Responder for CourseOfferingResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = serde_json::to_string(&self).unwrap();
+
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+}
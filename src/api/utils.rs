@@ -2,12 +2,25 @@ use regex::Regex;
 
 pub fn validate_email(email: &str) -> bool {
     let email_regex = Regex::new(r"^([a-z0-9_+]([a-z0-9_+.]*[a-z0-9_+])?)@([a-z0-9]+([\-\.]{1}[a-z0-9]+)*\.[a-z]{2,6})").unwrap();
-    email_regex.is_match(&email)
+    email_regex.is_match(email)
+}
+
+pub fn validate_phone_number(phone_number: &str) -> bool {
+    let phone_regex = Regex::new(r"^\+?[0-9(]([0-9 ()\-]*[0-9])?$").unwrap();
+    let digits = phone_number.chars().filter(|c| c.is_ascii_digit()).count();
+    phone_regex.is_match(phone_number) && (7..=15).contains(&digits)
+}
+
+pub fn compute_gpa(graded: &[(f64, i32)]) -> Option<f64> {
+    let total_credits: i32 = graded.iter().map(|(_, credits)| credits).sum();
+    if total_credits == 0 { return None; }
+    let total_points: f64 = graded.iter().map(|(points, credits)| points * *credits as f64).sum();
+    Some((total_points / total_credits as f64 * 100.0).round() / 100.0)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::api::utils::validate_email;
+    use crate::api::utils::{compute_gpa, validate_email, validate_phone_number};
 
     #[test]
     fn test_valid_email_format() {
@@ -18,4 +31,31 @@ mod tests {
     fn test_invalid_email_format() {
         assert_eq!(validate_email("invalid email"), false);
     }
+
+    #[test]
+    fn test_valid_phone_number_format() {
+        assert!(validate_phone_number("91234567"));
+        assert!(validate_phone_number("+65 9123-4567"));
+        assert!(validate_phone_number("(555) 123-4567"));
+    }
+
+    #[test]
+    fn test_invalid_phone_number_format() {
+        assert!(!validate_phone_number("12345"));
+        assert!(!validate_phone_number("call me"));
+        assert!(!validate_phone_number("9123 4567 ext"));
+        assert!(!validate_phone_number("+1234567890123456"));
+    }
+
+    #[test]
+    fn test_compute_gpa_weighted_by_credits() {
+        assert_eq!(compute_gpa(&[(4.0, 3), (3.0, 1)]), Some(3.75));
+        assert_eq!(compute_gpa(&[(3.3, 2), (2.0, 1), (0.0, 0)]), Some(2.87));
+    }
+
+    #[test]
+    fn test_compute_gpa_without_credits() {
+        assert_eq!(compute_gpa(&[]), None);
+        assert_eq!(compute_gpa(&[(4.0, 0)]), None);
+    }
 }
\ No newline at end of file
@@ -1,22 +1,120 @@
 use std::env;
+use std::str::FromStr;
+use std::time::Duration;
 
+use actix_web::{rt, web};
 use diesel::{prelude::*, r2d2};
+use diesel::connection::{AnsiTransactionManager, TransactionManager};
 use diesel::pg::PgConnection;
 use dotenvy::dotenv;
 
+use crate::api::errors::ApiError;
+use crate::api::metrics::collectors::PoolMetrics;
+use crate::db::audit::context as audit_context;
+
 pub mod students;
 pub mod courses;
 pub mod students_courses;
+pub mod course_prerequisites;
+pub mod terms;
+pub mod course_offerings;
+pub mod instructors;
+pub mod course_meetings;
+pub mod search;
+pub mod api_keys;
+pub mod audit;
 
 pub type DbPool = r2d2::Pool<r2d2::ConnectionManager<PgConnection>>;
+pub type DbConnection = r2d2::PooledConnection<r2d2::ConnectionManager<PgConnection>>;
+
+const MAX_STARTUP_BACKOFF: Duration = Duration::from_secs(30);
+
+pub struct PoolConfig {
+    pub max_size: u32,
+    pub min_idle: Option<u32>,
+    pub connection_timeout: Duration,
+    pub idle_timeout: Option<Duration>,
+    pub startup_attempts: u32,
+    pub startup_backoff: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        PoolConfig {
+            max_size: 10,
+            min_idle: None,
+            connection_timeout: Duration::from_secs(5),
+            idle_timeout: Some(Duration::from_secs(600)),
+            startup_attempts: 10,
+            startup_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn from_env() -> Self {
+        dotenv().ok();
+        let default = PoolConfig::default();
+        PoolConfig {
+            max_size: env_or("DB_POOL_MAX_SIZE", default.max_size),
+            min_idle: env_var("DB_POOL_MIN_IDLE").or(default.min_idle),
+            connection_timeout: env_var("DB_POOL_TIMEOUT_MS").map(Duration::from_millis).unwrap_or(default.connection_timeout),
+            // 0 keeps idle connections open for good
+            idle_timeout: match env_var::<u64>("DB_POOL_IDLE_TIMEOUT_SECS") {
+                Some(0) => None,
+                Some(seconds) => Some(Duration::from_secs(seconds)),
+                None => default.idle_timeout,
+            },
+            startup_attempts: env_or("DB_STARTUP_ATTEMPTS", default.startup_attempts).max(1),
+            startup_backoff: env_var("DB_STARTUP_BACKOFF_MS").map(Duration::from_millis).unwrap_or(default.startup_backoff),
+        }
+    }
+}
+
+fn env_var<T: FromStr>(name: &str) -> Option<T> {
+    let value = env::var(name).ok()?;
+    Some(value.parse().unwrap_or_else(|_| panic!("{} should be a non-negative number, got {}", name, value)))
+}
+
+fn env_or<T: FromStr>(name: &str, default: T) -> T {
+    env_var(name).unwrap_or(default)
+}
 
 pub fn initialize_db_pool() -> DbPool {
+    build_db_pool(&PoolConfig::from_env())
+}
+
+// the pool is built without connecting, so a database that is still starting up does not take the process down
+pub fn build_db_pool(config: &PoolConfig) -> DbPool {
     dotenv().ok();
     let conn_spec = env::var("DATABASE_URL").expect("DATABASE_URL should be set");
     let manager = r2d2::ConnectionManager::<PgConnection>::new(conn_spec);
     r2d2::Pool::builder()
-        .build(manager)
-        .expect("database URL should be valid path to Postgres instance")
+        .max_size(config.max_size)
+        .min_idle(config.min_idle)
+        .connection_timeout(config.connection_timeout)
+        .idle_timeout(config.idle_timeout)
+        .event_handler(Box::new(PoolMetrics))
+        .build_unchecked(manager)
+}
+
+// retries with exponential backoff so the app can be started before Postgres is accepting connections
+pub async fn wait_for_connection(pool: &DbPool, config: &PoolConfig) -> Result<DbConnection, r2d2::PoolError> {
+    let mut backoff = config.startup_backoff;
+    let mut attempt = 1;
+    loop {
+        let pool = pool.clone();
+        match web::block(move || pool.get()).await.expect("blocking pool should not be shut down") {
+            Ok(conn) => return Ok(conn),
+            Err(error) if attempt >= config.startup_attempts => return Err(error),
+            Err(error) => {
+                eprintln!("database is not reachable yet (attempt {} of {}), retrying in {:?}: {}", attempt, config.startup_attempts, backoff, error.to_string().trim_end());
+                rt::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_STARTUP_BACKOFF);
+                attempt += 1;
+            }
+        }
+    }
 }
 
 pub fn establish_connection() -> PgConnection {
@@ -27,3 +125,31 @@ pub fn establish_connection() -> PgConnection {
         .unwrap_or_else(|_| panic!("Error connecting to {}", database_url))
 }
 
+// diesel is synchronous, so queries run on the blocking thread pool instead of stalling the actix worker
+pub async fn run<T, F>(pool: &DbPool, f: F) -> Result<T, ApiError>
+    where
+        F: FnOnce(&mut PgConnection) -> Result<T, ApiError> + Send + 'static,
+        T: Send + 'static,
+{
+    let pool = pool.clone();
+    // task locals stay on the worker, the audit context is carried over to the blocking thread by hand
+    let context = audit_context::current();
+    web::block(move || {
+        let mut conn = pool.get()?;
+        audit_context::sync_scope(context, || f(&mut conn))
+    })
+        .await
+        .map_err(|_| ApiError::InternalError)?
+}
+
+pub fn run_serializable<T, E, F>(conn: &mut PgConnection, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut PgConnection) -> Result<T, E>,
+        E: From<diesel::result::Error>,
+{
+    // the isolation level can only be picked by the outermost transaction, nested calls fall back to a savepoint
+    match AnsiTransactionManager::transaction_manager_status_mut(conn).transaction_depth() {
+        Ok(None) => conn.build_transaction().serializable().run(f),
+        _ => conn.transaction(f),
+    }
+}
@@ -0,0 +1,44 @@
+use diesel::prelude::*;
+
+use crate::db::api_keys::models::ApiKey;
+use crate::schema::api_keys;
+
+pub fn get_active_api_key(conn: &mut PgConnection, key_hash: &str) -> QueryResult<Option<ApiKey>> {
+    api_keys::table
+        .filter(api_keys::key_hash.eq(key_hash))
+        .filter(api_keys::revoked_at.is_null())
+        .select(ApiKey::as_select())
+        .first(conn)
+        .optional()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+    use diesel::{Connection, result::Error};
+
+    use crate::db;
+    use crate::db::api_keys::models::Role;
+
+    use super::*;
+
+    #[test]
+    fn test_get_active_api_key() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            diesel::insert_into(api_keys::table)
+                .values((api_keys::name.eq("reporting"), api_keys::key_hash.eq("a".repeat(64)), api_keys::role.eq(Role::Registrar)))
+                .execute(conn)?;
+            let api_key = get_active_api_key(conn, &"a".repeat(64))?.unwrap();
+            assert_eq!("reporting", api_key.name);
+            assert_eq!(Role::Registrar, api_key.role);
+            assert!(get_active_api_key(conn, &"b".repeat(64))?.is_none());
+
+            diesel::update(api_keys::table.filter(api_keys::name.eq("reporting")))
+                .set(api_keys::revoked_at.eq(Local::now().naive_utc()))
+                .execute(conn)?;
+            assert!(get_active_api_key(conn, &"a".repeat(64))?.is_none());
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,62 @@
+use chrono::NaiveDateTime;
+use diesel::{AsExpression, FromSqlRow, Identifiable, Queryable, Selectable};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::api_keys;
+
+#[derive(Identifiable, Queryable, Selectable, PartialEq, Debug, Clone)]
+#[diesel(table_name = api_keys)]
+pub struct ApiKey {
+    pub id: i32,
+    pub name: String,
+    pub key_hash: String,
+    pub created_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub role: Role,
+}
+
+#[derive(AsExpression, FromSqlRow, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    Registrar,
+    Instructor,
+    Student,
+    Monitor,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Registrar => "registrar",
+            Role::Instructor => "instructor",
+            Role::Student => "student",
+            Role::Monitor => "monitor",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for Role {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), &mut out.reborrow())
+    }
+}
+
+impl FromSql<Text, Pg> for Role {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+            "admin" => Ok(Role::Admin),
+            "registrar" => Ok(Role::Registrar),
+            "instructor" => Ok(Role::Instructor),
+            "student" => Ok(Role::Student),
+            "monitor" => Ok(Role::Monitor),
+            other => Err(format!("unrecognized role: {}", other).into()),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use std::future::Future;
+
+#[derive(Clone, Default, Debug)]
+pub struct AuditContext {
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+}
+
+tokio::task_local! {
+    static AUDIT_CONTEXT: AuditContext;
+}
+
+// changes made outside a request, like migrations or tests, are recorded without an actor
+pub fn current() -> AuditContext {
+    AUDIT_CONTEXT.try_with(|context| context.clone()).unwrap_or_default()
+}
+
+pub async fn scope<F: Future>(context: AuditContext, f: F) -> F::Output {
+    AUDIT_CONTEXT.scope(context, f).await
+}
+
+pub fn sync_scope<R>(context: AuditContext, f: impl FnOnce() -> R) -> R {
+    AUDIT_CONTEXT.sync_scope(context, f)
+}
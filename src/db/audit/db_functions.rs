@@ -0,0 +1,118 @@
+use diesel::pg::Pg;
+use diesel::prelude::*;
+
+use crate::db::audit::context;
+use crate::db::audit::models::{AuditAction, AuditEvent, AuditEventFilter, Audited, NewAuditEvent};
+use crate::schema::audit_events;
+
+pub fn record_create<T: Audited>(conn: &mut PgConnection, after: &T) -> QueryResult<()> {
+    record(conn, AuditAction::Create, after.entity_id(), None, Some(after))
+}
+
+pub fn record_update<T: Audited>(conn: &mut PgConnection, before: &T, after: &T) -> QueryResult<()> {
+    record(conn, AuditAction::Update, after.entity_id(), Some(before), Some(after))
+}
+
+pub fn record_delete<T: Audited>(conn: &mut PgConnection, before: &T) -> QueryResult<()> {
+    record(conn, AuditAction::Delete, before.entity_id(), Some(before), None)
+}
+
+pub fn record_deletes<T: Audited>(conn: &mut PgConnection, deleted: &[T]) -> QueryResult<()> {
+    deleted.iter().try_for_each(|x| record_delete(conn, x))
+}
+
+fn record<T: Audited>(conn: &mut PgConnection, action: AuditAction, entity_id: String, before: Option<&T>, after: Option<&T>) -> QueryResult<()> {
+    let context = context::current();
+    let event = NewAuditEvent {
+        actor: context.actor,
+        action,
+        entity_type: T::ENTITY_TYPE,
+        entity_id,
+        before: before.map(serde_json::to_value).transpose().map_err(|x| diesel::result::Error::SerializationError(Box::new(x)))?,
+        after: after.map(serde_json::to_value).transpose().map_err(|x| diesel::result::Error::SerializationError(Box::new(x)))?,
+        request_id: context.request_id,
+    };
+    diesel::insert_into(audit_events::table).values(event).execute(conn).map(|_| ())
+}
+
+// newest events first, so the cursor moves back in time
+pub fn list_audit_events(conn: &mut PgConnection, filter: &AuditEventFilter, limit: i64, offset: i64, before_id: Option<i32>) -> QueryResult<Vec<AuditEvent>> {
+    let mut query = filtered(audit_events::table.select(AuditEvent::as_select()).into_boxed(), filter);
+    if let Some(cursor) = before_id {
+        query = query.filter(audit_events::id.lt(cursor));
+    }
+    query.order(audit_events::id.desc()).limit(limit).offset(offset).load(conn)
+}
+
+pub fn count_audit_events(conn: &mut PgConnection, filter: &AuditEventFilter) -> QueryResult<i64> {
+    filtered(audit_events::table.count().into_boxed(), filter).get_result(conn)
+}
+
+fn filtered<'a, ST: 'a>(mut query: audit_events::BoxedQuery<'a, Pg, ST>, filter: &'a AuditEventFilter) -> audit_events::BoxedQuery<'a, Pg, ST> {
+    if let Some(actor) = &filter.actor {
+        query = query.filter(audit_events::actor.eq(actor));
+    }
+    if let Some(action) = filter.action {
+        query = query.filter(audit_events::action.eq(action));
+    }
+    if let Some(entity_type) = &filter.entity_type {
+        query = query.filter(audit_events::entity_type.eq(entity_type));
+    }
+    if let Some(entity_id) = &filter.entity_id {
+        query = query.filter(audit_events::entity_id.eq(entity_id));
+    }
+    if let Some(request_id) = &filter.request_id {
+        query = query.filter(audit_events::request_id.eq(request_id));
+    }
+    if let Some(since) = filter.since {
+        query = query.filter(audit_events::created_at.ge(since));
+    }
+    if let Some(until) = filter.until {
+        query = query.filter(audit_events::created_at.lt(until));
+    }
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::{Connection, result::Error};
+
+    use crate::db;
+    use crate::db::audit::context::{self, AuditContext};
+    use crate::db::students::db_functions as students_db_functions;
+    use crate::db::students::models::{NewStudent, UpdateStudent};
+
+    use super::*;
+
+    #[test]
+    fn test_mutations_are_audited() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let audit_context = AuditContext { actor: Some(String::from("registrar@gmail.com")), request_id: Some(String::from("audit-test-request")) };
+            let student = context::sync_scope(audit_context, || {
+                let student = students_db_functions::create_student(conn, NewStudent { email: String::from("audited_user@gmail.com"), ..Default::default() })?;
+                let changes = UpdateStudent { phone_number: Some(Some(String::from("+65 9123 4567"))), ..Default::default() };
+                students_db_functions::update_student(conn, student.id, changes)?;
+                students_db_functions::delete_student(conn, &student.email)?;
+                Ok::<_, Error>(student)
+            })?;
+
+            let filter = AuditEventFilter { request_id: Some(String::from("audit-test-request")), ..Default::default() };
+            let events = list_audit_events(conn, &filter, 10, 0, None)?;
+            assert_eq!(3, count_audit_events(conn, &filter)?);
+            assert_eq!(vec![AuditAction::Delete, AuditAction::Update, AuditAction::Create], events.iter().map(|x| x.action).collect::<Vec<AuditAction>>());
+            assert!(events.iter().all(|x| x.entity_type == "student" && x.entity_id == student.id.to_string()));
+            assert!(events.iter().all(|x| x.actor.as_deref() == Some("registrar@gmail.com")));
+            assert_eq!(None, events[2].before);
+            assert_eq!("audited_user@gmail.com", events[2].after.as_ref().unwrap()["email"]);
+            assert_eq!("+65 9123 4567", events[1].after.as_ref().unwrap()["phone_number"]);
+            assert_eq!(None, events[0].after);
+
+            let filter = AuditEventFilter { request_id: Some(String::from("audit-test-request")), action: Some(AuditAction::Update), ..Default::default() };
+            assert_eq!(vec![events[1].id], list_audit_events(conn, &filter, 10, 0, None)?.into_iter().map(|x| x.id).collect::<Vec<i32>>());
+            let filter = AuditEventFilter { request_id: Some(String::from("audit-test-request")), ..Default::default() };
+            assert_eq!(vec![events[2].id], list_audit_events(conn, &filter, 10, 0, Some(events[1].id))?.into_iter().map(|x| x.id).collect::<Vec<i32>>());
+            Ok(())
+        });
+    }
+}
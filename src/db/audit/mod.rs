@@ -0,0 +1,3 @@
+pub mod context;
+pub mod db_functions;
+pub mod models;
\ No newline at end of file
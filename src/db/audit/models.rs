@@ -0,0 +1,125 @@
+use chrono::NaiveDateTime;
+use diesel::{AsExpression, FromSqlRow, Identifiable, Insertable, Queryable, Selectable};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::db::courses::models::Course;
+use crate::db::students::models::Student;
+use crate::db::students_courses::models::{StudentCourse, WaitlistEntry};
+use crate::schema::audit_events;
+
+#[derive(Identifiable, Queryable, Selectable, PartialEq, Debug, Clone)]
+#[diesel(table_name = audit_events)]
+pub struct AuditEvent {
+    pub id: i32,
+    pub actor: Option<String>,
+    pub action: AuditAction,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub request_id: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = audit_events)]
+pub struct NewAuditEvent {
+    pub actor: Option<String>,
+    pub action: AuditAction,
+    pub entity_type: &'static str,
+    pub entity_id: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub request_id: Option<String>,
+}
+
+#[derive(Default)]
+pub struct AuditEventFilter {
+    pub actor: Option<String>,
+    pub action: Option<AuditAction>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub request_id: Option<String>,
+    pub since: Option<NaiveDateTime>,
+    pub until: Option<NaiveDateTime>,
+}
+
+#[derive(AsExpression, FromSqlRow, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for AuditAction {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), &mut out.reborrow())
+    }
+}
+
+impl FromSql<Text, Pg> for AuditAction {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+            "create" => Ok(AuditAction::Create),
+            "update" => Ok(AuditAction::Update),
+            "delete" => Ok(AuditAction::Delete),
+            other => Err(format!("unrecognized audit action: {}", other).into()),
+        }
+    }
+}
+
+pub trait Audited: Serialize {
+    const ENTITY_TYPE: &'static str;
+
+    fn entity_id(&self) -> String;
+}
+
+impl Audited for Student {
+    const ENTITY_TYPE: &'static str = "student";
+
+    fn entity_id(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl Audited for Course {
+    const ENTITY_TYPE: &'static str = "course";
+
+    fn entity_id(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+// enrollments are keyed by the student and the offering, written as student_id:offering_id
+impl Audited for StudentCourse {
+    const ENTITY_TYPE: &'static str = "student_course";
+
+    fn entity_id(&self) -> String {
+        format!("{}:{}", self.student_id, self.offering_id)
+    }
+}
+
+impl Audited for WaitlistEntry {
+    const ENTITY_TYPE: &'static str = "waitlist_entry";
+
+    fn entity_id(&self) -> String {
+        self.id.to_string()
+    }
+}
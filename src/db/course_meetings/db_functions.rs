@@ -0,0 +1,190 @@
+use diesel::prelude::*;
+
+use crate::db::course_meetings::models::{CourseMeeting, NewCourseMeeting, ScheduleConflict};
+use crate::db::course_offerings::models::CourseOffering;
+use crate::db::courses::models::Course;
+use crate::db::students_courses::models::EnrollmentStatus;
+use crate::schema::{course_meetings, course_offerings, courses, students_courses};
+
+pub fn create_course_meeting(conn: &mut PgConnection, new_meeting: NewCourseMeeting) -> QueryResult<CourseMeeting> {
+    diesel::insert_into(course_meetings::table)
+        .values(&new_meeting)
+        .returning(CourseMeeting::as_returning())
+        .get_result(conn)
+}
+
+pub fn get_meetings_for_offering(conn: &mut PgConnection, offering_id: i32) -> QueryResult<Vec<CourseMeeting>> {
+    let mut meetings: Vec<CourseMeeting> = course_meetings::table
+        .filter(course_meetings::offering_id.eq(offering_id))
+        .select(CourseMeeting::as_select())
+        .load(conn)?;
+    meetings.sort_by_key(|meeting| (meeting.day_of_week, meeting.start_time, meeting.id));
+    Ok(meetings)
+}
+
+pub fn delete_course_meeting(conn: &mut PgConnection, offering_id: i32, meeting_id: i32) -> QueryResult<usize> {
+    let predicate = course_meetings::id.eq(meeting_id).and(course_meetings::offering_id.eq(offering_id));
+    diesel::delete(course_meetings::table.filter(predicate)).execute(conn)
+}
+
+pub fn delete_meetings_for_course(conn: &mut PgConnection, course_id: i32) -> QueryResult<usize> {
+    diesel::delete(course_meetings::table.filter(course_meetings::course_id.eq(course_id))).execute(conn)
+}
+
+pub fn get_schedule_for_student(conn: &mut PgConnection, student_id: i32, term_id: i32) -> QueryResult<Vec<(CourseMeeting, Course)>> {
+    get_enrolled_meetings(conn, student_id, Some(term_id))
+}
+
+// only classes of the same term can clash, a course taken in another term never overlaps
+pub fn get_schedule_conflicts(conn: &mut PgConnection, student_id: i32, offering: &CourseOffering) -> QueryResult<Vec<ScheduleConflict>> {
+    let requested = get_meetings_for_offering(conn, offering.id)?;
+    let conflicts = get_enrolled_meetings(conn, student_id, Some(offering.term_id))?
+        .into_iter()
+        .filter(|(_, course)| course.id != offering.course_id)
+        .flat_map(|(existing, course)| {
+            requested.iter()
+                .filter(|meeting| meeting.overlaps(&existing))
+                .map(|meeting| ScheduleConflict { course: course.clone(), existing: existing.clone(), requested: meeting.clone() })
+                .collect::<Vec<ScheduleConflict>>()
+        })
+        .collect();
+    Ok(conflicts)
+}
+
+fn get_enrolled_meetings(conn: &mut PgConnection, student_id: i32, term_id: Option<i32>) -> QueryResult<Vec<(CourseMeeting, Course)>> {
+    let mut query = students_courses::table
+        .filter(students_courses::student_id.eq(student_id))
+        .filter(students_courses::status.eq(EnrollmentStatus::Enrolled))
+        .inner_join(course_offerings::table)
+        .inner_join(courses::table)
+        .inner_join(course_meetings::table.on(course_meetings::offering_id.eq(students_courses::offering_id)))
+        .select((CourseMeeting::as_select(), Course::as_select()))
+        .into_boxed();
+    if let Some(term_id) = term_id {
+        query = query.filter(course_offerings::term_id.eq(term_id));
+    }
+    // days are stored as text, so the weekly order is restored here rather than in SQL
+    let mut schedule = query.load::<(CourseMeeting, Course)>(conn)?;
+    schedule.sort_by_key(|(meeting, _)| (meeting.day_of_week, meeting.start_time, meeting.id));
+    Ok(schedule)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime};
+    use diesel::{Connection, result::Error};
+
+    use crate::db;
+    use crate::db::course_offerings::db_functions as course_offerings_db_functions;
+    use crate::db::course_offerings::models::NewCourseOffering;
+    use crate::db::course_meetings::models::DayOfWeek;
+    use crate::db::courses::db_functions as courses_db_functions;
+    use crate::db::courses::models::NewCourse;
+    use crate::db::students::db_functions as students_db_functions;
+    use crate::db::students::models::NewStudent;
+    use crate::db::students_courses::db_functions as students_courses_db_functions;
+    use crate::db::terms::db_functions as terms_db_functions;
+    use crate::db::terms::models::NewTerm;
+
+    use super::*;
+
+    fn meeting(offering: &CourseOffering, day_of_week: DayOfWeek, start: (u32, u32), end: (u32, u32)) -> NewCourseMeeting {
+        NewCourseMeeting {
+            course_id: offering.course_id,
+            offering_id: offering.id,
+            day_of_week,
+            start_time: NaiveTime::from_hms_opt(start.0, start.1, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(end.0, end.1, 0).unwrap(),
+            room: None,
+        }
+    }
+
+    #[test]
+    fn test_create_course_meeting() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_course = NewCourse { name: String::from("data science"), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let offering = course_offerings_db_functions::tests::open_offering(conn, course.id)?;
+            create_course_meeting(conn, meeting(&offering, DayOfWeek::Wednesday, (9, 0), (10, 30)))?;
+            let monday = create_course_meeting(conn, meeting(&offering, DayOfWeek::Monday, (9, 0), (10, 30)))?;
+
+            let meetings = get_meetings_for_offering(conn, offering.id)?;
+            assert_eq!(vec![DayOfWeek::Monday, DayOfWeek::Wednesday], meetings.iter().map(|x| x.day_of_week).collect::<Vec<DayOfWeek>>());
+            assert_eq!(1, delete_course_meeting(conn, offering.id, monday.id)?);
+            assert_eq!(1, delete_meetings_for_course(conn, course.id)?);
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_create_course_meeting_ends_before_start() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_course = NewCourse { name: String::from("data science"), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let offering = course_offerings_db_functions::tests::open_offering(conn, course.id)?;
+            create_course_meeting(conn, meeting(&offering, DayOfWeek::Monday, (10, 0), (9, 0)))?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_schedule_conflicts() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("data science"), ..Default::default() };
+            let enrolled = courses_db_functions::create_course(conn, new_course)?;
+            let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
+            let clashing = courses_db_functions::create_course(conn, new_course)?;
+            let new_course = NewCourse { name: String::from("statistics"), ..Default::default() };
+            let back_to_back = courses_db_functions::create_course(conn, new_course)?;
+            let enrolled_offering = course_offerings_db_functions::tests::open_offering(conn, enrolled.id)?;
+            let clashing_offering = course_offerings_db_functions::tests::open_offering(conn, clashing.id)?;
+            let back_to_back_offering = course_offerings_db_functions::tests::open_offering(conn, back_to_back.id)?;
+            create_course_meeting(conn, meeting(&enrolled_offering, DayOfWeek::Monday, (9, 0), (10, 30)))?;
+            create_course_meeting(conn, meeting(&clashing_offering, DayOfWeek::Monday, (10, 0), (11, 0)))?;
+            create_course_meeting(conn, meeting(&back_to_back_offering, DayOfWeek::Monday, (10, 30), (11, 30)))?;
+            students_courses_db_functions::create_student_course(conn, student.id, &enrolled_offering)?.unwrap();
+
+            let conflicts = get_schedule_conflicts(conn, student.id, &clashing_offering)?;
+            assert_eq!(vec!["data science"], conflicts.into_iter().map(|x| x.course.name).collect::<Vec<String>>());
+            assert!(get_schedule_conflicts(conn, student.id, &back_to_back_offering)?.is_empty());
+
+            students_courses_db_functions::update_student_course_status(conn, student.id, enrolled_offering.id, EnrollmentStatus::Dropped)?;
+            assert!(get_schedule_conflicts(conn, student.id, &clashing_offering)?.is_empty());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_schedule_conflicts_in_another_term() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("data science"), ..Default::default() };
+            let enrolled = courses_db_functions::create_course(conn, new_course)?;
+            let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
+            let later = courses_db_functions::create_course(conn, new_course)?;
+            let new_term = NewTerm { name: String::from("spring 2027"), starts_on: NaiveDate::from_ymd_opt(2027, 1, 11).unwrap(), ends_on: NaiveDate::from_ymd_opt(2027, 5, 7).unwrap() };
+            let spring = terms_db_functions::create_term(conn, new_term)?;
+            let enrolled_offering = course_offerings_db_functions::tests::open_offering(conn, enrolled.id)?;
+            let later_offering = course_offerings_db_functions::create_course_offering(conn, NewCourseOffering { course_id: later.id, term_id: spring.id, ..Default::default() })?;
+            create_course_meeting(conn, meeting(&enrolled_offering, DayOfWeek::Monday, (9, 0), (10, 30)))?;
+            create_course_meeting(conn, meeting(&later_offering, DayOfWeek::Monday, (9, 0), (10, 30)))?;
+            students_courses_db_functions::create_student_course(conn, student.id, &enrolled_offering)?.unwrap();
+
+            assert!(get_schedule_conflicts(conn, student.id, &later_offering)?.is_empty());
+
+            students_courses_db_functions::create_student_course(conn, student.id, &later_offering)?.unwrap();
+            let names = |schedule: Vec<(CourseMeeting, Course)>| schedule.into_iter().map(|(_, x)| x.name).collect::<Vec<String>>();
+            assert_eq!(vec!["data science"], names(get_schedule_for_student(conn, student.id, enrolled_offering.term_id)?));
+            assert_eq!(vec!["machine learning"], names(get_schedule_for_student(conn, student.id, spring.id)?));
+            Ok(())
+        })
+    }
+}
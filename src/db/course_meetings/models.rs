@@ -0,0 +1,97 @@
+use chrono::NaiveTime;
+use diesel::{AsExpression, Associations, FromSqlRow, Identifiable, Insertable, Queryable, Selectable};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
+
+use crate::db::course_offerings::models::CourseOffering;
+use crate::db::courses::models::Course;
+use crate::schema::course_meetings;
+
+#[derive(Identifiable, Selectable, Queryable, Associations, PartialEq, Debug, Clone)]
+#[diesel(belongs_to(Course))]
+#[diesel(belongs_to(CourseOffering, foreign_key = offering_id))]
+#[diesel(table_name = course_meetings)]
+pub struct CourseMeeting {
+    pub id: i32,
+    pub course_id: i32,
+    pub day_of_week: DayOfWeek,
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+    pub room: Option<String>,
+    pub offering_id: i32,
+}
+
+impl CourseMeeting {
+    // slots are half-open, so a class ending at 10:00 does not clash with one starting at 10:00
+    pub fn overlaps(&self, other: &CourseMeeting) -> bool {
+        self.day_of_week == other.day_of_week && self.start_time < other.end_time && other.start_time < self.end_time
+    }
+}
+
+#[derive(Insertable, Clone)]
+#[diesel(table_name = course_meetings)]
+pub struct NewCourseMeeting {
+    pub course_id: i32,
+    pub offering_id: i32,
+    pub day_of_week: DayOfWeek,
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+    pub room: Option<String>,
+}
+
+#[derive(AsExpression, FromSqlRow, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum DayOfWeek {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl DayOfWeek {
+    const ALL: [DayOfWeek; 7] = [
+        DayOfWeek::Monday, DayOfWeek::Tuesday, DayOfWeek::Wednesday, DayOfWeek::Thursday,
+        DayOfWeek::Friday, DayOfWeek::Saturday, DayOfWeek::Sunday,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DayOfWeek::Monday => "monday",
+            DayOfWeek::Tuesday => "tuesday",
+            DayOfWeek::Wednesday => "wednesday",
+            DayOfWeek::Thursday => "thursday",
+            DayOfWeek::Friday => "friday",
+            DayOfWeek::Saturday => "saturday",
+            DayOfWeek::Sunday => "sunday",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for DayOfWeek {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), &mut out.reborrow())
+    }
+}
+
+impl FromSql<Text, Pg> for DayOfWeek {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+        DayOfWeek::ALL.into_iter()
+            .find(|day| day.as_str() == value)
+            .ok_or_else(|| format!("unrecognized day of week: {}", value).into())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduleConflict {
+    pub course: Course,
+    pub existing: CourseMeeting,
+    pub requested: CourseMeeting,
+}
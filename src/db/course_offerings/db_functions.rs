@@ -0,0 +1,125 @@
+use diesel::prelude::*;
+
+use crate::db::course_offerings::models::{CourseOffering, NewCourseOffering};
+use crate::db::courses::models::Course;
+use crate::db::terms::models::Term;
+use crate::schema::{course_offerings, courses, terms};
+
+pub fn create_course_offering(conn: &mut PgConnection, new_offering: NewCourseOffering) -> QueryResult<CourseOffering> {
+    diesel::insert_into(course_offerings::table)
+        .values(&new_offering)
+        .returning(CourseOffering::as_returning())
+        .get_result(conn)
+}
+
+pub fn get_course_offering(conn: &mut PgConnection, offering_id: i32) -> QueryResult<CourseOffering> {
+    course_offerings::table.find(offering_id)
+        .select(CourseOffering::as_select())
+        .first(conn)
+}
+
+pub fn get_offering_for_course(conn: &mut PgConnection, course_id: i32, offering_id: i32) -> QueryResult<CourseOffering> {
+    course_offerings::table.find(offering_id)
+        .filter(course_offerings::course_id.eq(course_id))
+        .select(CourseOffering::as_select())
+        .first(conn)
+}
+
+pub fn get_offerings_for_term(conn: &mut PgConnection, term: &Term) -> QueryResult<Vec<(CourseOffering, Course)>> {
+    CourseOffering::belonging_to(term)
+        .inner_join(courses::table)
+        .select((CourseOffering::as_select(), Course::as_select()))
+        .order((courses::name, course_offerings::section))
+        .load(conn)
+}
+
+pub fn get_open_offerings_for_course(conn: &mut PgConnection, course_id: i32) -> QueryResult<Vec<CourseOffering>> {
+    course_offerings::table
+        .inner_join(terms::table)
+        .filter(course_offerings::course_id.eq(course_id))
+        .filter(terms::registration_open.eq(true))
+        .select(CourseOffering::as_select())
+        .order(course_offerings::id)
+        .load(conn)
+}
+
+pub fn delete_offerings_for_course(conn: &mut PgConnection, course_id: i32) -> QueryResult<usize> {
+    diesel::delete(course_offerings::table.filter(course_offerings::course_id.eq(course_id))).execute(conn)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use chrono::NaiveDate;
+    use diesel::{Connection, result::Error};
+
+    use crate::db;
+    use crate::db::courses::db_functions as courses_db_functions;
+    use crate::db::courses::models::NewCourse;
+    use crate::db::terms::db_functions as terms_db_functions;
+    use crate::db::terms::models::NewTerm;
+
+    use super::*;
+
+    pub const TEST_TERM: &str = "test term";
+
+    // gives the course a single section, with the course capacity, in a shared term that is open for registration
+    pub fn open_offering(conn: &mut PgConnection, course_id: i32) -> QueryResult<CourseOffering> {
+        let course = courses_db_functions::get_course_by_id(conn, course_id)?;
+        let new_term = NewTerm { name: String::from(TEST_TERM), starts_on: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 12, 31).unwrap() };
+        diesel::insert_into(terms::table)
+            .values((&new_term, terms::registration_open.eq(true)))
+            .on_conflict(terms::name)
+            .do_nothing()
+            .execute(conn)?;
+        let term_id = terms::table.filter(terms::name.eq(TEST_TERM)).select(terms::id).first(conn)?;
+        diesel::insert_into(course_offerings::table)
+            .values(NewCourseOffering { course_id, term_id, section: None, capacity: course.capacity })
+            .on_conflict((course_offerings::course_id, course_offerings::term_id, course_offerings::section))
+            .do_nothing()
+            .execute(conn)?;
+        course_offerings::table
+            .filter(course_offerings::course_id.eq(course_id))
+            .filter(course_offerings::term_id.eq(term_id))
+            .select(CourseOffering::as_select())
+            .first(conn)
+    }
+
+    #[test]
+    fn test_offer_course_in_several_terms() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_course = NewCourse { name: String::from("mathematics"), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let new_term = NewTerm { name: String::from("fall 2026"), starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 12, 18).unwrap() };
+            let fall = terms_db_functions::create_term(conn, new_term)?;
+            let new_term = NewTerm { name: String::from("spring 2027"), starts_on: NaiveDate::from_ymd_opt(2027, 1, 11).unwrap(), ends_on: NaiveDate::from_ymd_opt(2027, 5, 7).unwrap() };
+            let spring = terms_db_functions::create_term(conn, new_term)?;
+
+            create_course_offering(conn, NewCourseOffering { course_id: course.id, term_id: fall.id, ..Default::default() })?;
+            create_course_offering(conn, NewCourseOffering { course_id: course.id, term_id: fall.id, section: Some(String::from("002")), capacity: Some(20) })?;
+            create_course_offering(conn, NewCourseOffering { course_id: course.id, term_id: spring.id, ..Default::default() })?;
+
+            let offerings = get_offerings_for_term(conn, &fall)?;
+            assert_eq!(vec!["001", "002"], offerings.iter().map(|(offering, _)| offering.section.as_str()).collect::<Vec<&str>>());
+            assert_eq!(1, get_offerings_for_term(conn, &spring)?.len());
+            assert_eq!(3, delete_offerings_for_course(conn, course.id)?);
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_create_course_offering_duplicate_section() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_course = NewCourse { name: String::from("mathematics"), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let new_term = NewTerm { name: String::from("fall 2026"), starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 12, 18).unwrap() };
+            let term = terms_db_functions::create_term(conn, new_term)?;
+
+            create_course_offering(conn, NewCourseOffering { course_id: course.id, term_id: term.id, ..Default::default() })?;
+            create_course_offering(conn, NewCourseOffering { course_id: course.id, term_id: term.id, ..Default::default() })?;
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use diesel::{Associations, Identifiable, Insertable, Queryable, Selectable};
+
+use crate::db::courses::models::Course;
+use crate::db::terms::models::Term;
+use crate::schema::course_offerings;
+
+#[derive(Identifiable, Queryable, Selectable, Associations, PartialEq, Debug, Clone)]
+#[diesel(belongs_to(Course))]
+#[diesel(belongs_to(Term))]
+#[diesel(table_name = course_offerings)]
+pub struct CourseOffering {
+    pub id: i32,
+    pub course_id: i32,
+    pub term_id: i32,
+    pub section: String,
+    pub capacity: Option<i32>,
+}
+
+#[derive(Insertable, Clone, Default)]
+#[diesel(table_name = course_offerings)]
+pub struct NewCourseOffering {
+    pub course_id: i32,
+    pub term_id: i32,
+    pub section: Option<String>,
+    pub capacity: Option<i32>,
+}
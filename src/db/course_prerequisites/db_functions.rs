@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+
+use diesel::prelude::*;
+
+use crate::db;
+use crate::db::course_prerequisites::models::CoursePrerequisite;
+use crate::db::courses::models::Course;
+use crate::db::students_courses::models::EnrollmentStatus;
+use crate::schema::*;
+
+pub fn get_prerequisites(conn: &mut PgConnection, course_id: i32) -> QueryResult<Vec<Course>> {
+    course_prerequisites::table
+        .filter(course_prerequisites::course_id.eq(course_id))
+        .inner_join(courses::table)
+        .select(Course::as_select())
+        .order(courses::id)
+        .load(conn)
+}
+
+pub fn create_course_prerequisite(conn: &mut PgConnection, course_id: i32, prerequisite_id: i32) -> QueryResult<CoursePrerequisite> {
+    diesel::insert_into(course_prerequisites::table)
+        .values((
+            course_prerequisites::course_id.eq(course_id),
+            course_prerequisites::prerequisite_id.eq(prerequisite_id)
+        ))
+        .returning(CoursePrerequisite::as_returning())
+        .get_result(conn)
+}
+
+pub fn delete_course_prerequisite(conn: &mut PgConnection, course_id: i32, prerequisite_id: i32) -> QueryResult<usize> {
+    diesel::delete(course_prerequisites::table.find((course_id, prerequisite_id))).execute(conn)
+}
+
+pub fn delete_prerequisites_for_course(conn: &mut PgConnection, course_id: i32) -> QueryResult<usize> {
+    let predicate = course_prerequisites::course_id.eq(course_id).or(course_prerequisites::prerequisite_id.eq(course_id));
+    diesel::delete(course_prerequisites::table.filter(predicate)).execute(conn)
+}
+
+pub fn would_create_cycle(conn: &mut PgConnection, course_id: i32, prerequisite_id: i32) -> QueryResult<bool> {
+    // walks everything the new prerequisite already requires; reaching the course means the edge closes a loop
+    let mut visited = HashSet::from([prerequisite_id]);
+    let mut frontier = vec![prerequisite_id];
+    while !frontier.is_empty() {
+        if frontier.contains(&course_id) { return Ok(true); }
+        let next: Vec<i32> = course_prerequisites::table
+            .filter(course_prerequisites::course_id.eq_any(&frontier))
+            .select(course_prerequisites::prerequisite_id)
+            .load(conn)?;
+        frontier = next.into_iter().filter(|id| visited.insert(*id)).collect();
+    }
+    Ok(false)
+}
+
+pub fn add_course_prerequisite(conn: &mut PgConnection, course_id: i32, prerequisite_id: i32) -> QueryResult<Option<CoursePrerequisite>> {
+    db::run_serializable(conn, |conn| {
+        if would_create_cycle(conn, course_id, prerequisite_id)? { return Ok(None); }
+        create_course_prerequisite(conn, course_id, prerequisite_id).map(Some)
+    })
+}
+
+pub fn get_missing_prerequisites(conn: &mut PgConnection, student_id: i32, course_id: i32) -> QueryResult<Vec<Course>> {
+    let completed = students_courses::table
+        .filter(students_courses::student_id.eq(student_id))
+        .filter(students_courses::status.eq(EnrollmentStatus::Completed))
+        .select(students_courses::course_id);
+    course_prerequisites::table
+        .filter(course_prerequisites::course_id.eq(course_id))
+        .filter(course_prerequisites::prerequisite_id.ne_all(completed))
+        .inner_join(courses::table)
+        .select(Course::as_select())
+        .order(courses::id)
+        .load(conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::{Connection, result::Error};
+
+    use crate::db;
+    use crate::db::course_offerings::db_functions as course_offerings_db_functions;
+    use crate::db::courses::db_functions as courses_db_functions;
+    use crate::db::courses::models::NewCourse;
+    use crate::db::students::db_functions as students_db_functions;
+    use crate::db::students::models::NewStudent;
+    use crate::db::students_courses::db_functions as students_courses_db_functions;
+    use crate::db::students_courses::models::SignUpRejection;
+
+    use super::*;
+
+    #[test]
+    fn test_get_prerequisites() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let algebra = courses_db_functions::create_course(conn, NewCourse { name: String::from("algebra"), ..Default::default() })?;
+            let calculus = courses_db_functions::create_course(conn, NewCourse { name: String::from("calculus"), ..Default::default() })?;
+            create_course_prerequisite(conn, calculus.id, algebra.id)?;
+
+            let prerequisites = get_prerequisites(conn, calculus.id)?;
+            assert_eq!(vec!["algebra"], prerequisites.into_iter().map(|item| item.name).collect::<Vec<String>>());
+            assert_eq!(1, delete_course_prerequisite(conn, calculus.id, algebra.id)?);
+            assert!(get_prerequisites(conn, calculus.id)?.is_empty());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_course_prerequisite_rejects_cycle() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let algebra = courses_db_functions::create_course(conn, NewCourse { name: String::from("algebra"), ..Default::default() })?;
+            let calculus = courses_db_functions::create_course(conn, NewCourse { name: String::from("calculus"), ..Default::default() })?;
+            let analysis = courses_db_functions::create_course(conn, NewCourse { name: String::from("analysis"), ..Default::default() })?;
+            assert!(add_course_prerequisite(conn, calculus.id, algebra.id)?.is_some());
+            assert!(add_course_prerequisite(conn, analysis.id, calculus.id)?.is_some());
+
+            assert!(add_course_prerequisite(conn, algebra.id, analysis.id)?.is_none());
+            assert!(add_course_prerequisite(conn, algebra.id, algebra.id)?.is_none());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_missing_prerequisites() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let student = students_db_functions::create_student(conn, NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() })?;
+            let algebra = courses_db_functions::create_course(conn, NewCourse { name: String::from("algebra"), ..Default::default() })?;
+            let geometry = courses_db_functions::create_course(conn, NewCourse { name: String::from("geometry"), ..Default::default() })?;
+            let calculus = courses_db_functions::create_course(conn, NewCourse { name: String::from("calculus"), ..Default::default() })?;
+            create_course_prerequisite(conn, calculus.id, algebra.id)?;
+            create_course_prerequisite(conn, calculus.id, geometry.id)?;
+            let algebra_offering = course_offerings_db_functions::tests::open_offering(conn, algebra.id)?;
+            students_courses_db_functions::create_student_course(conn, student.id, &algebra_offering)?.unwrap();
+            students_courses_db_functions::tests::sign_up_for_course(conn, student.id, geometry.id)?;
+            students_courses_db_functions::update_student_course_status(conn, student.id, algebra_offering.id, EnrollmentStatus::Completed)?;
+
+            let missing = get_missing_prerequisites(conn, student.id, calculus.id)?;
+            assert_eq!(vec!["geometry"], missing.into_iter().map(|item| item.name).collect::<Vec<String>>());
+            Ok(())
+        })
+    }
+    #[test]
+    fn test_create_student_course_missing_prerequisites() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let student = students_db_functions::create_student(conn, NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() })?;
+            let algebra = courses_db_functions::create_course(conn, NewCourse { name: String::from("algebra"), ..Default::default() })?;
+            let calculus = courses_db_functions::create_course(conn, NewCourse { name: String::from("calculus"), ..Default::default() })?;
+            create_course_prerequisite(conn, calculus.id, algebra.id)?;
+            let calculus_offering = course_offerings_db_functions::tests::open_offering(conn, calculus.id)?;
+
+            match students_courses_db_functions::create_student_course(conn, student.id, &calculus_offering)? {
+                Err(SignUpRejection::MissingPrerequisites(missing)) => assert_eq!(vec!["algebra"], missing.into_iter().map(|item| item.name).collect::<Vec<String>>()),
+                _ => panic!("expected missing prerequisites"),
+            }
+            assert!(students_courses_db_functions::get_student_course(conn, student.id, calculus_offering.id)?.is_none());
+            Ok(())
+        })
+    }
+}
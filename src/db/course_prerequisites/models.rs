@@ -0,0 +1,13 @@
+use diesel::{Associations, Identifiable, Queryable, Selectable};
+
+use crate::db::courses::models::Course;
+use crate::schema::course_prerequisites;
+
+#[derive(Identifiable, Selectable, Queryable, Associations, Debug, Clone)]
+#[diesel(belongs_to(Course))]
+#[diesel(table_name = course_prerequisites)]
+#[diesel(primary_key(course_id, prerequisite_id))]
+pub struct CoursePrerequisite {
+    pub course_id: i32,
+    pub prerequisite_id: i32,
+}
@@ -1,13 +1,19 @@
 use diesel::prelude::*;
 
-use crate::db::courses::models::{Course, NewCourse};
-use crate::schema::courses;
+use crate::db::audit::db_functions as audit_db_functions;
+use crate::db::courses::models::{Course, CourseSortField, NewCourse};
+use crate::db::students_courses::models::EnrollmentStatus;
+use crate::schema::{courses, students_courses};
 
 pub fn create_course(conn: &mut PgConnection, new_course: NewCourse) -> QueryResult<Course> {
-    diesel::insert_into(courses::table)
-        .values(&new_course)
-        .returning(Course::as_returning())
-        .get_result(conn)
+    conn.transaction(|conn| {
+        let course = diesel::insert_into(courses::table)
+            .values(&new_course)
+            .returning(Course::as_returning())
+            .get_result(conn)?;
+        audit_db_functions::record_create(conn, &course)?;
+        Ok(course)
+    })
 }
 
 pub fn get_course(conn: &mut PgConnection, name: &str) -> QueryResult<Course> {
@@ -16,9 +22,58 @@ pub fn get_course(conn: &mut PgConnection, name: &str) -> QueryResult<Course> {
         .first(conn)
 }
 
+pub fn get_course_by_id(conn: &mut PgConnection, course_id: i32) -> QueryResult<Course> {
+    courses::table.find(course_id)
+        .select(Course::as_select())
+        .first(conn)
+}
+
+pub fn get_courses_by_names(conn: &mut PgConnection, names: &[String]) -> QueryResult<Vec<Course>> {
+    courses::table.filter(courses::name.eq_any(names))
+        .select(Course::as_select())
+        .load(conn)
+}
+
+pub fn list_courses(conn: &mut PgConnection, sort: CourseSortField, limit: i64, offset: i64, after_id: Option<i32>) -> QueryResult<Vec<Course>> {
+    let mut query = courses::table.select(Course::as_select()).into_boxed();
+    if let Some(cursor) = after_id {
+        query = query.filter(courses::id.gt(cursor));
+    }
+    query = match sort {
+        CourseSortField::Id => query.order(courses::id),
+        CourseSortField::Name => query.order((courses::name, courses::id)),
+    };
+    query.limit(limit).offset(offset).load(conn)
+}
+
+pub fn list_courses_for_export(conn: &mut PgConnection, student_id: Option<i32>, after_id: Option<i32>, limit: i64) -> QueryResult<Vec<Course>> {
+    let mut query = courses::table.select(Course::as_select()).into_boxed();
+    if let Some(student_id) = student_id {
+        let attended = students_courses::table
+            .filter(students_courses::student_id.eq(student_id))
+            .filter(students_courses::status.ne(EnrollmentStatus::Dropped))
+            .select(students_courses::course_id);
+        query = query.filter(courses::id.eq_any(attended));
+    }
+    if let Some(cursor) = after_id {
+        query = query.filter(courses::id.gt(cursor));
+    }
+    query.order(courses::id).limit(limit).load(conn)
+}
+
+pub fn count_courses(conn: &mut PgConnection) -> QueryResult<i64> {
+    courses::table.count().get_result(conn)
+}
+
 pub fn delete_course(conn: &mut PgConnection, course_name: &str) -> QueryResult<usize> {
-    let predicate = courses::name.eq(course_name);
-    diesel::delete(courses::table.filter(predicate)).execute(conn)
+    conn.transaction(|conn| {
+        let predicate = courses::name.eq(course_name);
+        let deleted = diesel::delete(courses::table.filter(predicate))
+            .returning(Course::as_returning())
+            .get_results(conn)?;
+        audit_db_functions::record_deletes(conn, &deleted)?;
+        Ok(deleted.len())
+    })
 }
 
 #[cfg(test)]
@@ -52,13 +107,33 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_list_courses() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let total = count_courses(conn)?;
+            let first = create_course(conn, NewCourse { name: String::from("zoology"), ..Default::default() })?;
+            let second = create_course(conn, NewCourse { name: String::from("anthropology"), ..Default::default() })?;
+            assert_eq!(total + 2, count_courses(conn)?);
+
+            let page = list_courses(conn, CourseSortField::Id, 10, 0, Some(first.id - 1))?;
+            assert_eq!(vec![first.id, second.id], page.into_iter().map(|item| item.id).collect::<Vec<i32>>());
+
+            let page = list_courses(conn, CourseSortField::Id, 1, 0, Some(first.id - 1))?;
+            assert_eq!(vec![first.id], page.into_iter().map(|item| item.id).collect::<Vec<i32>>());
+            let page = list_courses(conn, CourseSortField::Id, 1, 0, Some(first.id))?;
+            assert_eq!(vec![second.id], page.into_iter().map(|item| item.id).collect::<Vec<i32>>());
+            Ok(())
+        });
+    }
+
     #[test]
     #[should_panic]
     fn test_get_course_not_found() {
         let mut conn = db::establish_connection();
         conn.test_transaction::<_, Error, _>(|conn| {
             let new_course = NewCourse { name: String::from("mathematics"), ..Default::default() };
-            let course = create_course(conn, new_course)?;
+            create_course(conn, new_course)?;
             let _course = get_course(conn, "physics")?;
             Ok(())
         });
@@ -1,14 +1,25 @@
-use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use diesel::{Identifiable, Insertable, Queryable, QueryableByName, Selectable};
+use serde::{Deserialize, Serialize};
 
 use crate::api::courses::types::CreateCourseRequest;
 use crate::schema::courses;
 
-#[derive(Identifiable, Queryable, Selectable, PartialEq, Debug, Clone)]
+#[derive(Identifiable, Queryable, QueryableByName, Selectable, Serialize, PartialEq, Debug, Clone)]
 #[diesel(table_name = courses)]
 pub struct Course {
     pub id: i32,
     pub name: String,
     pub course_desc: Option<String>,
+    pub capacity: Option<i32>,
+    pub credits: i32,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CourseSortField {
+    #[default]
+    Id,
+    Name,
 }
 
 #[derive(Insertable, Clone, Default)]
@@ -16,6 +27,8 @@ pub struct Course {
 pub struct NewCourse {
     pub name: String,
     pub course_desc: Option<String>,
+    pub capacity: Option<i32>,
+    pub credits: Option<i32>,
 }
 
 impl From<CreateCourseRequest> for NewCourse {
@@ -23,7 +36,8 @@ impl From<CreateCourseRequest> for NewCourse {
         Self {
             name: value.name,
             course_desc: value.course_desc,
-            ..Default::default()
+            capacity: value.capacity,
+            credits: value.credits,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,201 @@
+use diesel::prelude::*;
+
+use crate::db::courses::models::Course;
+use crate::db::instructors::models::{CourseInstructor, Instructor, InstructorSortField, NewInstructor, UpdateInstructor};
+use crate::schema::{course_instructors, courses, instructors};
+
+pub fn create_instructor(conn: &mut PgConnection, new_instructor: NewInstructor) -> QueryResult<Instructor> {
+    diesel::insert_into(instructors::table)
+        .values(&new_instructor)
+        .returning(Instructor::as_returning())
+        .get_result(conn)
+}
+
+pub fn get_instructor(conn: &mut PgConnection, email: &str) -> QueryResult<Instructor> {
+    instructors::table.filter(instructors::email.eq(email))
+        .select(Instructor::as_select())
+        .first(conn)
+}
+
+pub fn get_instructor_by_id(conn: &mut PgConnection, instructor_id: i32) -> QueryResult<Instructor> {
+    instructors::table.find(instructor_id)
+        .select(Instructor::as_select())
+        .first(conn)
+}
+
+pub fn list_instructors(conn: &mut PgConnection, sort: InstructorSortField, limit: i64, offset: i64, after_id: Option<i32>) -> QueryResult<Vec<Instructor>> {
+    let mut query = instructors::table.select(Instructor::as_select()).into_boxed();
+    if let Some(cursor) = after_id {
+        query = query.filter(instructors::id.gt(cursor));
+    }
+    query = match sort {
+        InstructorSortField::Id => query.order(instructors::id),
+        InstructorSortField::Name => query.order((instructors::name, instructors::id)),
+        InstructorSortField::Email => query.order((instructors::email, instructors::id)),
+    };
+    query.limit(limit).offset(offset).load(conn)
+}
+
+pub fn count_instructors(conn: &mut PgConnection) -> QueryResult<i64> {
+    instructors::table.count().get_result(conn)
+}
+
+pub fn update_instructor(conn: &mut PgConnection, instructor_id: i32, changes: UpdateInstructor) -> QueryResult<Instructor> {
+    diesel::update(instructors::table.find(instructor_id))
+        .set(&changes)
+        .returning(Instructor::as_returning())
+        .get_result(conn)
+}
+
+pub fn delete_instructor(conn: &mut PgConnection, instructor_email: &str) -> QueryResult<usize> {
+    let predicate = instructors::email.eq(instructor_email);
+    diesel::delete(instructors::table.filter(predicate)).execute(conn)
+}
+
+pub fn assign_instructor_to_course(conn: &mut PgConnection, course_id: i32, instructor_id: i32) -> QueryResult<CourseInstructor> {
+    diesel::insert_into(course_instructors::table)
+        .values((
+            course_instructors::course_id.eq(course_id),
+            course_instructors::instructor_id.eq(instructor_id)
+        ))
+        .returning(CourseInstructor::as_returning())
+        .get_result(conn)
+}
+
+pub fn unassign_instructor_from_course(conn: &mut PgConnection, course_id: i32, instructor_id: i32) -> QueryResult<usize> {
+    diesel::delete(course_instructors::table.find((course_id, instructor_id))).execute(conn)
+}
+
+pub fn get_instructors_for_course(conn: &mut PgConnection, course: &Course) -> QueryResult<Vec<Instructor>> {
+    CourseInstructor::belonging_to(course)
+        .inner_join(instructors::table)
+        .select(Instructor::as_select())
+        .order(instructors::id)
+        .load(conn)
+}
+
+pub fn get_courses_for_instructor(conn: &mut PgConnection, instructor: &Instructor) -> QueryResult<Vec<Course>> {
+    CourseInstructor::belonging_to(instructor)
+        .inner_join(courses::table)
+        .select(Course::as_select())
+        .order(courses::id)
+        .load(conn)
+}
+
+pub fn is_assigned_to_course(conn: &mut PgConnection, instructor_email: &str, course_id: i32) -> QueryResult<bool> {
+    diesel::select(diesel::dsl::exists(
+        course_instructors::table
+            .inner_join(instructors::table)
+            .filter(instructors::email.eq(instructor_email))
+            .filter(course_instructors::course_id.eq(course_id))
+    )).get_result(conn)
+}
+
+pub fn delete_assignments_for_instructor(conn: &mut PgConnection, instructor_id: i32) -> QueryResult<usize> {
+    diesel::delete(course_instructors::table.filter(course_instructors::instructor_id.eq(instructor_id))).execute(conn)
+}
+
+pub fn delete_assignments_for_course(conn: &mut PgConnection, course_id: i32) -> QueryResult<usize> {
+    diesel::delete(course_instructors::table.filter(course_instructors::course_id.eq(course_id))).execute(conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::{Connection, result::Error};
+
+    use crate::db;
+    use crate::db::courses::db_functions as courses_db_functions;
+    use crate::db::courses::models::NewCourse;
+
+    use super::*;
+
+    #[test]
+    fn test_create_instructor() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_instructor = NewInstructor { name: String::from("Jane Doe"), email: String::from("jane.doe@gmail.com"), ..Default::default() };
+            let instructor = create_instructor(conn, new_instructor)?;
+            assert_eq!(instructor, get_instructor(conn, "jane.doe@gmail.com")?);
+            assert_eq!(instructor, get_instructor_by_id(conn, instructor.id)?);
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_create_instructor_duplicate() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_instructor = NewInstructor { name: String::from("Jane Doe"), email: String::from("jane.doe@gmail.com"), ..Default::default() };
+            create_instructor(conn, new_instructor.clone())?;
+            create_instructor(conn, new_instructor)?;
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_update_instructor() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_instructor = NewInstructor { name: String::from("Jane Doe"), email: String::from("jane.doe@gmail.com"), ..Default::default() };
+            let instructor = create_instructor(conn, new_instructor)?;
+            let changes = UpdateInstructor { name: Some(String::from("Jane Smith")), ..Default::default() };
+            let updated = update_instructor(conn, instructor.id, changes)?;
+            assert_eq!(("Jane Smith", "jane.doe@gmail.com"), (updated.name.as_str(), updated.email.as_str()));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_list_instructors() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            for (name, email) in [("Zoe", "zoe@gmail.com"), ("Adam", "adam@gmail.com")] {
+                let new_instructor = NewInstructor { name: String::from(name), email: String::from(email), ..Default::default() };
+                create_instructor(conn, new_instructor)?;
+            }
+            let total = count_instructors(conn)?;
+            let instructors = list_instructors(conn, InstructorSortField::Name, total, 0, None)?;
+            let names = instructors.into_iter().map(|x| x.name).filter(|x| x == "Zoe" || x == "Adam").collect::<Vec<String>>();
+            assert_eq!(vec!["Adam", "Zoe"], names);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_delete_instructor() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_instructor = NewInstructor { name: String::from("Jane Doe"), email: String::from("jane.doe@gmail.com"), ..Default::default() };
+            create_instructor(conn, new_instructor)?;
+            assert_eq!(1, delete_instructor(conn, "jane.doe@gmail.com")?);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_assign_instructor_to_course() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_instructor = NewInstructor { name: String::from("Jane Doe"), email: String::from("jane.doe@gmail.com"), ..Default::default() };
+            let instructor = create_instructor(conn, new_instructor)?;
+            let new_course = NewCourse { name: String::from("data science"), ..Default::default() };
+            let course_one = courses_db_functions::create_course(conn, new_course)?;
+            let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
+            let course_two = courses_db_functions::create_course(conn, new_course)?;
+            assign_instructor_to_course(conn, course_one.id, instructor.id)?;
+            assign_instructor_to_course(conn, course_two.id, instructor.id)?;
+
+            assert_eq!(vec!["data science", "machine learning"], get_courses_for_instructor(conn, &instructor)?.into_iter().map(|x| x.name).collect::<Vec<String>>());
+            assert_eq!(vec![instructor.clone()], get_instructors_for_course(conn, &course_one)?);
+            assert!(is_assigned_to_course(conn, "jane.doe@gmail.com", course_one.id)?);
+            assert!(!is_assigned_to_course(conn, "john.doe@gmail.com", course_one.id)?);
+
+            assert_eq!(1, unassign_instructor_from_course(conn, course_one.id, instructor.id)?);
+            assert!(get_instructors_for_course(conn, &course_one)?.is_empty());
+            assert_eq!(1, delete_assignments_for_course(conn, course_two.id)?);
+            assert_eq!(0, delete_assignments_for_instructor(conn, instructor.id)?);
+            Ok(())
+        });
+    }
+}
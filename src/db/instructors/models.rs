@@ -0,0 +1,74 @@
+use chrono::prelude::*;
+use diesel::{AsChangeset, Associations, Identifiable, Insertable, Queryable, Selectable};
+use serde::Deserialize;
+
+use crate::api::instructors::types::{CreateInstructorRequest, UpdateInstructorRequest};
+use crate::db::courses::models::Course;
+use crate::schema::{course_instructors, instructors};
+
+#[derive(Identifiable, Queryable, Selectable, PartialEq, Debug, Clone)]
+#[diesel(table_name = instructors)]
+pub struct Instructor {
+    pub id: i32,
+    pub name: String,
+    pub email: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InstructorSortField {
+    #[default]
+    Id,
+    Name,
+    Email,
+}
+
+#[derive(Insertable, Clone, Default)]
+#[diesel(table_name = instructors)]
+pub struct NewInstructor {
+    pub name: String,
+    pub email: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+impl From<CreateInstructorRequest> for NewInstructor {
+    fn from(value: CreateInstructorRequest) -> Self {
+        Self {
+            name: value.name,
+            email: value.email,
+            created_at: Some(Local::now().naive_utc()),
+            updated_at: Some(Local::now().naive_utc()),
+        }
+    }
+}
+
+#[derive(AsChangeset, Clone, Default)]
+#[diesel(table_name = instructors)]
+pub struct UpdateInstructor {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+impl From<UpdateInstructorRequest> for UpdateInstructor {
+    fn from(value: UpdateInstructorRequest) -> Self {
+        Self {
+            name: value.name,
+            email: value.email,
+            updated_at: Some(Local::now().naive_utc()),
+        }
+    }
+}
+
+#[derive(Identifiable, Selectable, Queryable, Associations, Debug, Clone)]
+#[diesel(belongs_to(Course))]
+#[diesel(belongs_to(Instructor))]
+#[diesel(table_name = course_instructors)]
+#[diesel(primary_key(course_id, instructor_id))]
+pub struct CourseInstructor {
+    pub course_id: i32,
+    pub instructor_id: i32,
+}
@@ -0,0 +1,115 @@
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+
+use crate::db::search::models::{CourseMatch, StudentMatch};
+
+// exact matches rank first, then prefixes, then substrings, with trigram similarity catching typos
+pub fn search_students(conn: &mut PgConnection, term: &str, limit: i64) -> QueryResult<Vec<StudentMatch>> {
+    diesel::sql_query("\
+        SELECT students.*, GREATEST(
+            CASE WHEN lower(email) = lower($1) THEN 1.0
+                 WHEN email ILIKE $2 THEN 0.9
+                 WHEN phone_number ILIKE $2 THEN 0.8
+                 WHEN email ILIKE $3 THEN 0.6
+                 ELSE 0.0 END,
+            similarity(email, $1),
+            similarity(coalesce(phone_number, ''), $1)
+        )::REAL AS score
+        FROM students
+        WHERE email ILIKE $3 OR phone_number ILIKE $2 OR email % $1 OR phone_number % $1
+        ORDER BY score DESC, id
+        LIMIT $4")
+        .bind::<Text, _>(term)
+        .bind::<Text, _>(prefix_pattern(term))
+        .bind::<Text, _>(contains_pattern(term))
+        .bind::<BigInt, _>(limit)
+        .load(conn)
+}
+
+// descriptions are matched on whole words through the full-text index, or on similar words through trigrams
+pub fn search_courses(conn: &mut PgConnection, term: &str, limit: i64) -> QueryResult<Vec<CourseMatch>> {
+    diesel::sql_query("\
+        SELECT courses.*, GREATEST(
+            CASE WHEN lower(name) = lower($1) THEN 1.0
+                 WHEN name ILIKE $2 THEN 0.9
+                 WHEN name ILIKE $3 THEN 0.6
+                 WHEN to_tsvector('simple', name || ' ' || coalesce(course_desc, '')) @@ plainto_tsquery('simple', $1) THEN 0.5
+                 ELSE 0.0 END,
+            similarity(name, $1),
+            word_similarity($1, coalesce(course_desc, '')) * 0.5
+        )::REAL AS score
+        FROM courses
+        WHERE name ILIKE $3 OR name % $1 OR $1 <% course_desc
+           OR to_tsvector('simple', name || ' ' || coalesce(course_desc, '')) @@ plainto_tsquery('simple', $1)
+        ORDER BY score DESC, id
+        LIMIT $4")
+        .bind::<Text, _>(term)
+        .bind::<Text, _>(prefix_pattern(term))
+        .bind::<Text, _>(contains_pattern(term))
+        .bind::<BigInt, _>(limit)
+        .load(conn)
+}
+
+fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+fn prefix_pattern(term: &str) -> String {
+    format!("{}%", escape_like(term))
+}
+
+fn contains_pattern(term: &str) -> String {
+    format!("%{}%", escape_like(term))
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::{Connection, result::Error};
+
+    use crate::db;
+    use crate::db::courses::db_functions as courses_db_functions;
+    use crate::db::courses::models::NewCourse;
+    use crate::db::students::db_functions as students_db_functions;
+    use crate::db::students::models::NewStudent;
+
+    use super::*;
+
+    #[test]
+    fn test_search_students() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let exact = students_db_functions::create_student(conn, NewStudent { email: String::from("searchable_user@gmail.com"), ..Default::default() })?;
+            let prefixed = students_db_functions::create_student(conn, NewStudent { email: String::from("searchable_user_two@gmail.com"), phone_number: Some(String::from("+65 9123 4567")), ..Default::default() })?;
+
+            let matches = search_students(conn, "SEARCHABLE_USER@gmail.com", 10)?;
+            assert_eq!(exact.id, matches[0].student.id);
+            assert_eq!(1.0, matches[0].score);
+            assert!(matches.iter().any(|x| x.student.id == prefixed.id));
+
+            let matches = search_students(conn, "+65 9123", 10)?;
+            assert_eq!(vec![prefixed.id], matches.into_iter().map(|x| x.student.id).collect::<Vec<i32>>());
+
+            let matches = search_students(conn, "%_", 10)?;
+            assert!(matches.is_empty());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_search_courses() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let course = courses_db_functions::create_course(conn, NewCourse { name: String::from("searchable quantum mechanics"), course_desc: Some(String::from("waves and particles")), ..Default::default() })?;
+
+            let matches = search_courses(conn, "Searchable Quantum", 10)?;
+            assert_eq!(course.id, matches[0].course.id);
+
+            let matches = search_courses(conn, "searchable quantom mechanics", 10)?;
+            assert_eq!(course.id, matches[0].course.id);
+
+            let matches = search_courses(conn, "particles", 10)?;
+            assert!(matches.iter().any(|x| x.course.id == course.id));
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,2 @@
+pub mod db_functions;
+pub mod models;
\ No newline at end of file
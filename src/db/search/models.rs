@@ -0,0 +1,21 @@
+use diesel::QueryableByName;
+use diesel::sql_types::Float;
+
+use crate::db::courses::models::Course;
+use crate::db::students::models::Student;
+
+#[derive(QueryableByName, Debug, Clone)]
+pub struct StudentMatch {
+    #[diesel(embed)]
+    pub student: Student,
+    #[diesel(sql_type = Float)]
+    pub score: f32,
+}
+
+#[derive(QueryableByName, Debug, Clone)]
+pub struct CourseMatch {
+    #[diesel(embed)]
+    pub course: Course,
+    #[diesel(sql_type = Float)]
+    pub score: f32,
+}
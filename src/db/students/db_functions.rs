@@ -1,13 +1,19 @@
 use diesel::prelude::*;
 
-use crate::db::students::models::{NewStudent, Student};
-use crate::schema::students;
+use crate::db::audit::db_functions as audit_db_functions;
+use crate::db::students::models::{NewStudent, Student, StudentSortField, UpdateStudent};
+use crate::db::students_courses::models::EnrollmentStatus;
+use crate::schema::{students, students_courses};
 
 pub fn create_student(conn: &mut PgConnection, new_student: NewStudent) -> QueryResult<Student> {
-    diesel::insert_into(students::table)
-        .values(&new_student)
-        .returning(Student::as_returning())
-        .get_result(conn)
+    conn.transaction(|conn| {
+        let student = diesel::insert_into(students::table)
+            .values(&new_student)
+            .returning(Student::as_returning())
+            .get_result(conn)?;
+        audit_db_functions::record_create(conn, &student)?;
+        Ok(student)
+    })
 }
 
 pub fn get_student(conn: &mut PgConnection, email: &str) -> QueryResult<Student> {
@@ -16,9 +22,74 @@ pub fn get_student(conn: &mut PgConnection, email: &str) -> QueryResult<Student>
         .first(conn)
 }
 
+pub fn get_student_by_id(conn: &mut PgConnection, student_id: i32) -> QueryResult<Student> {
+    students::table.find(student_id)
+        .select(Student::as_select())
+        .first(conn)
+}
+
+pub fn get_students_by_emails(conn: &mut PgConnection, emails: &[String]) -> QueryResult<Vec<Student>> {
+    students::table.filter(students::email.eq_any(emails))
+        .select(Student::as_select())
+        .load(conn)
+}
+
+pub fn list_students(conn: &mut PgConnection, sort: StudentSortField, limit: i64, offset: i64, after_id: Option<i32>) -> QueryResult<Vec<Student>> {
+    let mut query = students::table.select(Student::as_select()).into_boxed();
+    if let Some(cursor) = after_id {
+        query = query.filter(students::id.gt(cursor));
+    }
+    query = match sort {
+        StudentSortField::Id => query.order(students::id),
+        StudentSortField::Email => query.order((students::email, students::id)),
+        StudentSortField::CreatedAt => query.order((students::created_at, students::id)),
+    };
+    query.limit(limit).offset(offset).load(conn)
+}
+
+pub fn list_students_for_export(conn: &mut PgConnection, course_id: Option<i32>, after_id: Option<i32>, limit: i64) -> QueryResult<Vec<Student>> {
+    let mut query = students::table.select(Student::as_select()).into_boxed();
+    if let Some(course_id) = course_id {
+        let enrolled = students_courses::table
+            .filter(students_courses::course_id.eq(course_id))
+            .filter(students_courses::status.ne(EnrollmentStatus::Dropped))
+            .select(students_courses::student_id);
+        query = query.filter(students::id.eq_any(enrolled));
+    }
+    if let Some(cursor) = after_id {
+        query = query.filter(students::id.gt(cursor));
+    }
+    query.order(students::id).limit(limit).load(conn)
+}
+
+pub fn count_students(conn: &mut PgConnection) -> QueryResult<i64> {
+    students::table.count().get_result(conn)
+}
+
+pub fn update_student(conn: &mut PgConnection, student_id: i32, changes: UpdateStudent) -> QueryResult<Student> {
+    conn.transaction(|conn| {
+        let before = students::table.find(student_id)
+            .select(Student::as_select())
+            .for_update()
+            .first(conn)?;
+        let student = diesel::update(students::table.find(student_id))
+            .set(&changes)
+            .returning(Student::as_returning())
+            .get_result(conn)?;
+        audit_db_functions::record_update(conn, &before, &student)?;
+        Ok(student)
+    })
+}
+
 pub fn delete_student(conn: &mut PgConnection, student_email: &str) -> QueryResult<usize> {
-    let predicate = students::email.eq(student_email);
-    diesel::delete(students::table.filter(predicate)).execute(conn)
+    conn.transaction(|conn| {
+        let predicate = students::email.eq(student_email);
+        let deleted = diesel::delete(students::table.filter(predicate))
+            .returning(Student::as_returning())
+            .get_results(conn)?;
+        audit_db_functions::record_deletes(conn, &deleted)?;
+        Ok(deleted.len())
+    })
 }
 
 #[cfg(test)]
@@ -64,6 +135,60 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_list_students() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let total = count_students(conn)?;
+            let first = create_student(conn, NewStudent { email: String::from("zz_test_user@gmail.com"), ..Default::default() })?;
+            let second = create_student(conn, NewStudent { email: String::from("aa_test_user@gmail.com"), ..Default::default() })?;
+            assert_eq!(total + 2, count_students(conn)?);
+
+            let page = list_students(conn, StudentSortField::Id, 10, 0, Some(first.id - 1))?;
+            assert_eq!(vec![first.id, second.id], page.into_iter().map(|item| item.id).collect::<Vec<i32>>());
+
+            let page = list_students(conn, StudentSortField::Id, 1, 0, Some(first.id - 1))?;
+            assert_eq!(vec![first.id], page.into_iter().map(|item| item.id).collect::<Vec<i32>>());
+            let page = list_students(conn, StudentSortField::Id, 1, 0, Some(first.id))?;
+            assert_eq!(vec![second.id], page.into_iter().map(|item| item.id).collect::<Vec<i32>>());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_update_student() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("test_user@gmail.com"), ..Default::default() };
+            let student = create_student(conn, new_student)?;
+            let changes = UpdateStudent { phone_number: Some(Some(String::from("91234567"))), ..Default::default() };
+            let student = update_student(conn, student.id, changes)?;
+            assert_eq!("test_user@gmail.com", student.email);
+            assert_eq!(Some(String::from("91234567")), student.phone_number);
+
+            let changes = UpdateStudent { email: Some(String::from("test_user@gmail.com")), ..Default::default() };
+            assert_eq!(Some(String::from("91234567")), update_student(conn, student.id, changes)?.phone_number);
+            let changes = UpdateStudent { phone_number: Some(None), ..Default::default() };
+            assert_eq!(None, update_student(conn, student.id, changes)?.phone_number);
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_update_student_not_unique_email() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("test_user@gmail.com"), ..Default::default() };
+            create_student(conn, new_student)?;
+            let new_student = NewStudent { email: String::from("test_user_two@gmail.com"), ..Default::default() };
+            let student = create_student(conn, new_student)?;
+            let changes = UpdateStudent { email: Some(String::from("test_user@gmail.com")), ..Default::default() };
+            update_student(conn, student.id, changes)?;
+            Ok(())
+        });
+    }
+
     #[test]
     #[should_panic]
     fn test_create_student_not_unique_email() {
@@ -1,10 +1,11 @@
 use chrono::prelude::*;
-use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable, QueryableByName, Selectable};
+use serde::{Deserialize, Serialize};
 
-use crate::api::students::types::CreateStudentRequest;
+use crate::api::students::types::{CreateStudentRequest, UpdateStudentRequest};
 use crate::schema::students;
 
-#[derive(Identifiable, Queryable, Selectable, PartialEq, Debug, Clone)]
+#[derive(Identifiable, Queryable, QueryableByName, Selectable, Serialize, PartialEq, Debug, Clone)]
 #[diesel(table_name = students)]
 pub struct Student {
     pub id: i32,
@@ -14,6 +15,15 @@ pub struct Student {
     pub updated_at: Option<NaiveDateTime>,
 }
 
+#[derive(Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StudentSortField {
+    #[default]
+    Id,
+    Email,
+    CreatedAt,
+}
+
 #[derive(Insertable, Clone, Default)]
 #[diesel(table_name = students)]
 pub struct NewStudent {
@@ -30,7 +40,24 @@ impl From<CreateStudentRequest> for NewStudent {
             phone_number: value.phone_number,
             created_at: Some(Local::now().naive_utc()),
             updated_at: Some(Local::now().naive_utc()),
-            ..Default::default()
+        }
+    }
+}
+
+#[derive(AsChangeset, Clone, Default)]
+#[diesel(table_name = students)]
+pub struct UpdateStudent {
+    pub email: Option<String>,
+    pub phone_number: Option<Option<String>>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+impl From<UpdateStudentRequest> for UpdateStudent {
+    fn from(value: UpdateStudentRequest) -> Self {
+        Self {
+            email: value.email,
+            phone_number: value.phone_number,
+            updated_at: Some(Local::now().naive_utc()),
         }
     }
 }
\ No newline at end of file
@@ -1,17 +1,28 @@
+use chrono::Local;
+use diesel::pg::Pg;
 use diesel::prelude::*;
+use diesel::sql_types::Bool;
 
+use crate::db::audit::db_functions as audit_db_functions;
+use crate::db::course_meetings::db_functions as course_meetings_db_functions;
+use crate::db::course_offerings::db_functions as course_offerings_db_functions;
+use crate::db::course_offerings::models::CourseOffering;
+use crate::db::course_prerequisites::db_functions as course_prerequisites_db_functions;
 use crate::db::courses::db_functions as courses_db_functions;
 use crate::db::courses::models::Course;
 use crate::db::students::db_functions as students_db_functions;
 use crate::db::students::models::Student;
-use crate::db::students_courses::models::StudentCourse;
+use crate::db;
+use crate::db::students_courses::models::{EnrollmentOutcome, EnrollmentStatus, Grade, SignUpRejection, StudentCourse, WaitlistEntry};
+use crate::db::terms::models::Term;
 use crate::schema::*;
 
-pub fn get_courses_attended_by_student(conn: &mut PgConnection, email: &str) -> QueryResult<Vec<Course>> {
+pub fn get_courses_attended_by_student(conn: &mut PgConnection, email: &str) -> QueryResult<Vec<(StudentCourse, Course)>> {
     let student = students_db_functions::get_student(conn, email)?;
     StudentCourse::belonging_to(&student)
         .inner_join(courses::table)
-        .select(Course::as_select())
+        .select((StudentCourse::as_select(), Course::as_select()))
+        .order((students_courses::status_changed_at, courses::id))
         .load(conn)
 }
 
@@ -23,33 +34,341 @@ pub fn get_students_in_course(conn: &mut PgConnection, name: &str) -> QueryResul
         .load(conn)
 }
 
-pub fn create_student_course(conn: &mut PgConnection, student_id: i32, course_id: i32) -> QueryResult<StudentCourse> {
-    diesel::insert_into(students_courses::table)
-        .values((
-            students_courses::student_id.eq(student_id),
-            students_courses::course_id.eq(course_id)
-        ))
-        .returning(StudentCourse::as_returning())
-        .get_result(conn)
+pub fn get_enrollments_for_student(conn: &mut PgConnection, student: &Student, status: Option<EnrollmentStatus>) -> QueryResult<Vec<(StudentCourse, Course)>> {
+    let mut query = StudentCourse::belonging_to(student)
+        .inner_join(courses::table)
+        .select((StudentCourse::as_select(), Course::as_select()))
+        .order(courses::id)
+        .into_boxed();
+    query = match status {
+        Some(status) => query.filter(students_courses::status.eq(status)),
+        None => query.filter(students_courses::status.ne(EnrollmentStatus::Dropped)),
+    };
+    query.load(conn)
+}
+
+pub fn get_enrollments_for_course(conn: &mut PgConnection, course: &Course, status: Option<EnrollmentStatus>) -> QueryResult<Vec<(StudentCourse, Student)>> {
+    let mut query = StudentCourse::belonging_to(course)
+        .inner_join(students::table)
+        .select((StudentCourse::as_select(), Student::as_select()))
+        .order(students::id)
+        .into_boxed();
+    query = match status {
+        Some(status) => query.filter(students_courses::status.eq(status)),
+        None => query.filter(students_courses::status.ne(EnrollmentStatus::Dropped)),
+    };
+    query.load(conn)
+}
+
+pub fn get_enrolled_courses_with_terms(conn: &mut PgConnection, student_id: i32) -> QueryResult<Vec<(StudentCourse, Course, Term)>> {
+    students_courses::table
+        .filter(students_courses::student_id.eq(student_id))
+        .filter(students_courses::status.eq(EnrollmentStatus::Enrolled))
+        .inner_join(courses::table)
+        .inner_join(course_offerings::table.inner_join(terms::table))
+        .select((StudentCourse::as_select(), Course::as_select(), Term::as_select()))
+        .order(courses::id)
+        .load(conn)
+}
+
+pub fn list_student_courses_for_export(conn: &mut PgConnection, student_id: Option<i32>, course_id: Option<i32>, after: Option<(i32, i32)>, limit: i64) -> QueryResult<Vec<(StudentCourse, Student, Course)>> {
+    let mut query = students_courses::table
+        .inner_join(students::table)
+        .inner_join(courses::table)
+        .select((StudentCourse::as_select(), Student::as_select(), Course::as_select()))
+        .into_boxed();
+    if let Some(student_id) = student_id {
+        query = query.filter(students_courses::student_id.eq(student_id));
+    }
+    if let Some(course_id) = course_id {
+        query = query.filter(students_courses::course_id.eq(course_id));
+    }
+    if let Some((after_student_id, after_offering_id)) = after {
+        query = query.filter(students_courses::student_id.gt(after_student_id)
+            .or(students_courses::student_id.eq(after_student_id).and(students_courses::offering_id.gt(after_offering_id))));
+    }
+    query.order((students_courses::student_id, students_courses::offering_id)).limit(limit).load(conn)
+}
+
+pub fn get_student_course(conn: &mut PgConnection, student_id: i32, offering_id: i32) -> QueryResult<Option<StudentCourse>> {
+    students_courses::table.find((student_id, offering_id))
+        .select(StudentCourse::as_select())
+        .first(conn)
+        .optional()
 }
 
-pub fn delete_student_course(conn: &mut PgConnection, student_id: i32, course_id: i32) -> QueryResult<usize> {
-    let predicate = students_courses::student_id.eq(student_id).and(students_courses::course_id.eq(course_id));
-    diesel::delete(students_courses::table.filter(predicate)).execute(conn)
+// the active enrollment if there is one, otherwise the one from the latest term the student took the course in
+pub fn get_current_student_course(conn: &mut PgConnection, student_id: i32, course_id: i32) -> QueryResult<Option<StudentCourse>> {
+    students_courses::table
+        .inner_join(course_offerings::table.inner_join(terms::table))
+        .filter(students_courses::student_id.eq(student_id))
+        .filter(students_courses::course_id.eq(course_id))
+        .order((students_courses::status.eq(EnrollmentStatus::Enrolled).desc(), terms::starts_on.desc(), students_courses::enrolled_at.desc()))
+        .select(StudentCourse::as_select())
+        .first(conn)
+        .optional()
+}
+
+pub fn create_student_course(conn: &mut PgConnection, student_id: i32, offering: &CourseOffering) -> QueryResult<Result<EnrollmentOutcome, SignUpRejection>> {
+    db::run_serializable(conn, |conn| {
+        lock_course(conn, offering.course_id)?;
+        let missing = course_prerequisites_db_functions::get_missing_prerequisites(conn, student_id, offering.course_id)?;
+        if !missing.is_empty() {
+            return Ok(Err(SignUpRejection::MissingPrerequisites(missing)));
+        }
+        let conflicts = course_meetings_db_functions::get_schedule_conflicts(conn, student_id, offering)?;
+        if !conflicts.is_empty() {
+            return Ok(Err(SignUpRejection::ScheduleConflict(conflicts)));
+        }
+        let existing = get_student_course(conn, student_id, offering.id)?;
+        let is_active = existing.as_ref().is_some_and(|x| matches!(x.status, EnrollmentStatus::Enrolled | EnrollmentStatus::Completed))
+            || is_enrolled_in_course(conn, student_id, offering.course_id)?;
+        if is_active || has_free_seat(conn, offering)? {
+            enroll(conn, student_id, offering, existing).map(|x| Ok(EnrollmentOutcome::Enrolled(x)))
+        } else {
+            let entry = diesel::insert_into(waitlist_entries::table)
+                .values((
+                    waitlist_entries::student_id.eq(student_id),
+                    waitlist_entries::course_id.eq(offering.course_id),
+                    waitlist_entries::offering_id.eq(offering.id)
+                ))
+                .returning(WaitlistEntry::as_returning())
+                .get_result(conn)?;
+            audit_db_functions::record_create(conn, &entry)?;
+            Ok(Ok(EnrollmentOutcome::Waitlisted(entry)))
+        }
+    })
+}
+
+pub fn update_student_course_status(conn: &mut PgConnection, student_id: i32, offering_id: i32, status: EnrollmentStatus) -> QueryResult<StudentCourse> {
+    db::run_serializable(conn, |conn| {
+        let before = get_student_course(conn, student_id, offering_id)?.ok_or(diesel::result::Error::NotFound)?;
+        lock_course(conn, before.course_id)?;
+        let updated = diesel::update(students_courses::table.find((student_id, offering_id)))
+            .set((
+                students_courses::status.eq(status),
+                students_courses::status_changed_at.eq(Local::now().naive_utc())
+            ))
+            .returning(StudentCourse::as_returning())
+            .get_result(conn)?;
+        audit_db_functions::record_update(conn, &before, &updated)?;
+        if before.status == EnrollmentStatus::Enrolled && status != EnrollmentStatus::Enrolled {
+            promote_from_waitlist(conn, before.course_id)?;
+        }
+        Ok(updated)
+    })
+}
+
+pub fn record_grade(conn: &mut PgConnection, student_id: i32, offering_id: i32, grade: Grade) -> QueryResult<StudentCourse> {
+    let status = grade.status();
+    let target = students_courses::table.find((student_id, offering_id));
+    db::run_serializable(conn, |conn| {
+        let before = get_student_course(conn, student_id, offering_id)?.ok_or(diesel::result::Error::NotFound)?;
+        lock_course(conn, before.course_id)?;
+        diesel::update(target.filter(students_courses::status.ne(status)))
+            .set(students_courses::status_changed_at.eq(Local::now().naive_utc()))
+            .execute(conn)?;
+        let updated = diesel::update(target)
+            .set((
+                students_courses::grade.eq(grade),
+                students_courses::status.eq(status)
+            ))
+            .returning(StudentCourse::as_returning())
+            .get_result(conn)?;
+        audit_db_functions::record_update(conn, &before, &updated)?;
+        if before.status == EnrollmentStatus::Enrolled {
+            promote_from_waitlist(conn, before.course_id)?;
+        }
+        Ok(updated)
+    })
+}
+
+// without an offering the student leaves the waitlist of every section of the course
+pub fn delete_waitlist_entry(conn: &mut PgConnection, student_id: i32, course_id: i32, offering_id: Option<i32>) -> QueryResult<usize> {
+    let predicate: Box<dyn BoxableExpression<waitlist_entries::table, Pg, SqlType=Bool>> = match offering_id {
+        Some(offering_id) => Box::new(waitlist_entries::student_id.eq(student_id).and(waitlist_entries::offering_id.eq(offering_id))),
+        None => Box::new(waitlist_entries::student_id.eq(student_id).and(waitlist_entries::course_id.eq(course_id))),
+    };
+    delete_waitlist_entries(conn, predicate)
+}
+
+pub fn delete_student_course(conn: &mut PgConnection, student_id: i32, offering: &CourseOffering) -> QueryResult<usize> {
+    db::run_serializable(conn, |conn| {
+        lock_course(conn, offering.course_id)?;
+        let predicate = students_courses::student_id.eq(student_id).and(students_courses::offering_id.eq(offering.id));
+        let deleted = delete_student_courses(conn, Box::new(predicate))?;
+        let unqueued = delete_waitlist_entry(conn, student_id, offering.course_id, Some(offering.id))?;
+        if deleted > 0 {
+            promote_from_waitlist(conn, offering.course_id)?;
+        }
+        Ok(deleted + unqueued)
+    })
+}
+
+// every offering of a course keeps its own seats, so entries are promoted in queue order whenever their offering has room;
+// students who got into another section or a clashing class in the meantime stay queued until they drop it,
+// as do students who no longer meet the prerequisites after those changed
+pub fn promote_from_waitlist(conn: &mut PgConnection, course_id: i32) -> QueryResult<Vec<StudentCourse>> {
+    db::run_serializable(conn, |conn| {
+        lock_course(conn, course_id)?;
+        let entries = waitlist_entries::table
+            .filter(waitlist_entries::course_id.eq(course_id))
+            .order((waitlist_entries::created_at, waitlist_entries::id))
+            .select(WaitlistEntry::as_select())
+            .load(conn)?;
+        let mut promoted = vec![];
+        for entry in entries {
+            let offering = course_offerings_db_functions::get_course_offering(conn, entry.offering_id)?;
+            if !has_free_seat(conn, &offering)? || is_enrolled_in_course(conn, entry.student_id, course_id)? { continue; }
+            if !course_meetings_db_functions::get_schedule_conflicts(conn, entry.student_id, &offering)?.is_empty() { continue; }
+            if !course_prerequisites_db_functions::get_missing_prerequisites(conn, entry.student_id, course_id)?.is_empty() { continue; }
+            delete_waitlist_entries(conn, Box::new(waitlist_entries::id.eq(entry.id)))?;
+            let existing = get_student_course(conn, entry.student_id, offering.id)?;
+            promoted.push(enroll(conn, entry.student_id, &offering, existing)?);
+        }
+        Ok(promoted)
+    })
+}
+
+pub fn get_waitlist_for_course(conn: &mut PgConnection, course: &Course) -> QueryResult<Vec<(WaitlistEntry, Student)>> {
+    WaitlistEntry::belonging_to(course)
+        .inner_join(students::table)
+        .select((WaitlistEntry::as_select(), Student::as_select()))
+        .order((waitlist_entries::created_at, waitlist_entries::id))
+        .load(conn)
+}
+
+// every course is listed, with 0 when nobody is enrolled
+pub fn count_enrolled_by_course(conn: &mut PgConnection) -> QueryResult<Vec<(String, i64)>> {
+    courses::table
+        .left_join(students_courses::table.on(students_courses::course_id.eq(courses::id).and(students_courses::status.eq(EnrollmentStatus::Enrolled))))
+        .group_by(courses::id)
+        .select((courses::name, diesel::dsl::count(students_courses::student_id.nullable())))
+        .order(courses::id)
+        .load(conn)
+}
+
+pub fn count_waitlisted_by_course(conn: &mut PgConnection) -> QueryResult<Vec<(String, i64)>> {
+    courses::table
+        .left_join(waitlist_entries::table)
+        .group_by(courses::id)
+        .select((courses::name, diesel::dsl::count(waitlist_entries::id.nullable())))
+        .order(courses::id)
+        .load(conn)
+}
+
+pub fn delete_waitlist_entries_for_student(conn: &mut PgConnection, student_id: i32) -> QueryResult<usize> {
+    delete_waitlist_entries(conn, Box::new(waitlist_entries::student_id.eq(student_id)))
+}
+
+pub fn delete_waitlist_entries_for_course(conn: &mut PgConnection, course_id: i32) -> QueryResult<usize> {
+    delete_waitlist_entries(conn, Box::new(waitlist_entries::course_id.eq(course_id)))
+}
+
+fn delete_waitlist_entries(conn: &mut PgConnection, predicate: Box<dyn BoxableExpression<waitlist_entries::table, Pg, SqlType=Bool>>) -> QueryResult<usize> {
+    conn.transaction(|conn| {
+        let deleted = diesel::delete(waitlist_entries::table.filter(predicate)).returning(WaitlistEntry::as_returning()).get_results(conn)?;
+        audit_db_functions::record_deletes(conn, &deleted)?;
+        Ok(deleted.len())
+    })
+}
+
+// earlier offerings keep their own rows, only a drop or fail in this same offering is re-activated
+fn enroll(conn: &mut PgConnection, student_id: i32, offering: &CourseOffering, existing: Option<StudentCourse>) -> QueryResult<StudentCourse> {
+    match existing {
+        Some(before) if matches!(before.status, EnrollmentStatus::Dropped | EnrollmentStatus::Failed) => {
+            let now = Local::now().naive_utc();
+            let updated = diesel::update(students_courses::table.find((student_id, offering.id)))
+                .set((
+                    students_courses::status.eq(EnrollmentStatus::Enrolled),
+                    students_courses::enrolled_at.eq(now),
+                    students_courses::status_changed_at.eq(now),
+                    students_courses::grade.eq(None::<Grade>)
+                ))
+                .returning(StudentCourse::as_returning())
+                .get_result(conn)?;
+            audit_db_functions::record_update(conn, &before, &updated)?;
+            Ok(updated)
+        }
+        // active enrollments fall through to the insert so the unique indexes report the duplicate
+        _ => {
+            let created = diesel::insert_into(students_courses::table)
+                .values((
+                    students_courses::student_id.eq(student_id),
+                    students_courses::course_id.eq(offering.course_id),
+                    students_courses::offering_id.eq(offering.id)
+                ))
+                .returning(StudentCourse::as_returning())
+                .get_result(conn)?;
+            audit_db_functions::record_create(conn, &created)?;
+            Ok(created)
+        }
+    }
+}
+
+fn lock_course(conn: &mut PgConnection, course_id: i32) -> QueryResult<Course> {
+    courses::table.find(course_id)
+        .select(Course::as_select())
+        .for_update()
+        .first(conn)
+}
+
+fn is_enrolled_in_course(conn: &mut PgConnection, student_id: i32, course_id: i32) -> QueryResult<bool> {
+    Ok(get_current_student_course(conn, student_id, course_id)?.is_some_and(|x| x.status == EnrollmentStatus::Enrolled))
+}
+
+fn has_free_seat(conn: &mut PgConnection, offering: &CourseOffering) -> QueryResult<bool> {
+    match offering.capacity {
+        Some(capacity) => {
+            let enrolled: i64 = students_courses::table
+                .filter(students_courses::offering_id.eq(offering.id))
+                .filter(students_courses::status.eq(EnrollmentStatus::Enrolled))
+                .count()
+                .get_result(conn)?;
+            Ok(enrolled < capacity as i64)
+        }
+        None => Ok(true),
+    }
+}
+
+pub fn delete_student_courses_for_student(conn: &mut PgConnection, student_id: i32) -> QueryResult<usize> {
+    delete_student_courses(conn, Box::new(students_courses::student_id.eq(student_id)))
+}
+
+pub fn delete_student_courses_for_course(conn: &mut PgConnection, course_id: i32) -> QueryResult<usize> {
+    delete_student_courses(conn, Box::new(students_courses::course_id.eq(course_id)))
+}
+
+fn delete_student_courses(conn: &mut PgConnection, predicate: Box<dyn BoxableExpression<students_courses::table, Pg, SqlType=Bool>>) -> QueryResult<usize> {
+    conn.transaction(|conn| {
+        let deleted = diesel::delete(students_courses::table.filter(predicate)).returning(StudentCourse::as_returning()).get_results(conn)?;
+        audit_db_functions::record_deletes(conn, &deleted)?;
+        Ok(deleted.len())
+    })
 }
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
+    use chrono::{NaiveDate, NaiveTime};
     use diesel::{Connection, result::Error};
 
     use crate::db;
+    use crate::db::course_meetings::models::{DayOfWeek, NewCourseMeeting};
+    use crate::db::course_offerings::models::NewCourseOffering;
     use crate::db::courses::db_functions as courses_db_functions;
     use crate::db::courses::models::NewCourse;
     use crate::db::students::db_functions as students_db_functions;
     use crate::db::students::models::NewStudent;
+    use crate::db::terms::db_functions as terms_db_functions;
+    use crate::db::terms::models::NewTerm;
 
     use super::*;
 
+    pub fn sign_up_for_course(conn: &mut PgConnection, student_id: i32, course_id: i32) -> QueryResult<EnrollmentOutcome> {
+        let offering = course_offerings_db_functions::tests::open_offering(conn, course_id)?;
+        Ok(create_student_course(conn, student_id, &offering)?.expect("sign up rejected"))
+    }
+
     #[test]
     fn test_get_courses_attended_by_students() {
         let mut conn = db::establish_connection();
@@ -60,11 +379,11 @@ mod tests {
             let course_one = courses_db_functions::create_course(conn, new_course)?;
             let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
             let course_two = courses_db_functions::create_course(conn, new_course)?;
-            create_student_course(conn, student.id, course_one.id)?;
-            create_student_course(conn, student.id, course_two.id)?;
+            sign_up_for_course(conn, student.id, course_one.id)?;
+            sign_up_for_course(conn, student.id, course_two.id)?;
 
             let courses_attended = get_courses_attended_by_student(conn, "some_user@gmail.com")?;
-            assert_eq!(vec!["data science", "machine learning"], courses_attended.into_iter().map(|item| item.name).collect::<Vec<String>>());
+            assert_eq!(vec!["data science", "machine learning"], courses_attended.into_iter().map(|(_, item)| item.name).collect::<Vec<String>>());
 
             Ok(())
         });
@@ -81,11 +400,11 @@ mod tests {
             let course_one = courses_db_functions::create_course(conn, new_course)?;
             let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
             let course_two = courses_db_functions::create_course(conn, new_course)?;
-            create_student_course(conn, student.id, course_one.id)?;
-            create_student_course(conn, student.id, course_two.id)?;
+            sign_up_for_course(conn, student.id, course_one.id)?;
+            sign_up_for_course(conn, student.id, course_two.id)?;
 
             let courses_attended = get_courses_attended_by_student(conn, "some_unknown_user@gmail.com")?;
-            assert_eq!(vec!["data science", "machine learning"], courses_attended.into_iter().map(|item| item.name).collect::<Vec<String>>());
+            assert_eq!(vec!["data science", "machine learning"], courses_attended.into_iter().map(|(_, item)| item.name).collect::<Vec<String>>());
 
             Ok(())
         });
@@ -101,8 +420,8 @@ mod tests {
             let student_two = students_db_functions::create_student(conn, new_student)?;
             let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
             let course = courses_db_functions::create_course(conn, new_course)?;
-            create_student_course(conn, student_one.id, course.id)?;
-            create_student_course(conn, student_two.id, course.id)?;
+            sign_up_for_course(conn, student_one.id, course.id)?;
+            sign_up_for_course(conn, student_two.id, course.id)?;
 
             let students_in_course = get_students_in_course(conn, "machine learning")?;
             assert_eq!(vec!["some_user@gmail.com", "some_user_two@gmail.com"], students_in_course.into_iter().map(|item| item.email).collect::<Vec<String>>());
@@ -122,8 +441,8 @@ mod tests {
             let student_two = students_db_functions::create_student(conn, new_student)?;
             let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
             let course = courses_db_functions::create_course(conn, new_course)?;
-            create_student_course(conn, student_one.id, course.id)?;
-            create_student_course(conn, student_two.id, course.id)?;
+            sign_up_for_course(conn, student_one.id, course.id)?;
+            sign_up_for_course(conn, student_two.id, course.id)?;
 
             let students_in_course = get_students_in_course(conn, "culinary")?;
             assert_eq!(vec!["some_user@gmail.com", "some_user_two@gmail.com"], students_in_course.into_iter().map(|item| item.email).collect::<Vec<String>>());
@@ -132,6 +451,47 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_get_enrollments_for_student_and_course() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            sign_up_for_course(conn, student.id, course.id)?;
+
+            let enrollments = get_enrollments_for_student(conn, &student, None)?;
+            assert_eq!(vec![(student.id, course.id, String::from("machine learning"))], enrollments.into_iter().map(|(sc, c)| (sc.student_id, sc.course_id, c.name)).collect::<Vec<_>>());
+
+            let enrollments = get_enrollments_for_course(conn, &course, None)?;
+            assert_eq!(vec![(student.id, course.id, String::from("some_user@gmail.com"))], enrollments.into_iter().map(|(sc, s)| (sc.student_id, sc.course_id, s.email)).collect::<Vec<_>>());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_list_student_courses_for_export() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let student = students_db_functions::create_student(conn, NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() })?;
+            let first = courses_db_functions::create_course(conn, NewCourse { name: String::from("machine learning"), ..Default::default() })?;
+            let second = courses_db_functions::create_course(conn, NewCourse { name: String::from("deep learning"), ..Default::default() })?;
+            let first_offering = course_offerings_db_functions::tests::open_offering(conn, first.id)?;
+            let second_offering = course_offerings_db_functions::tests::open_offering(conn, second.id)?;
+            create_student_course(conn, student.id, &first_offering)?.unwrap();
+            create_student_course(conn, student.id, &second_offering)?.unwrap();
+
+            let page = list_student_courses_for_export(conn, Some(student.id), None, None, 1)?;
+            assert_eq!(vec![first.id], page.iter().map(|(sc, _, _)| sc.course_id).collect::<Vec<i32>>());
+            let page = list_student_courses_for_export(conn, Some(student.id), None, Some((student.id, first_offering.id)), 10)?;
+            assert_eq!(vec![(second.id, String::from("some_user@gmail.com"), String::from("deep learning"))], page.into_iter().map(|(sc, s, c)| (sc.course_id, s.email, c.name)).collect::<Vec<_>>());
+            let page = list_student_courses_for_export(conn, None, Some(second.id), None, 10)?;
+            assert_eq!(1, page.len());
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_create_student_course() {
         let mut conn = db::establish_connection();
@@ -140,7 +500,7 @@ mod tests {
             let student = students_db_functions::create_student(conn, new_student)?;
             let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
             let course = courses_db_functions::create_course(conn, new_course)?;
-            create_student_course(conn, student.id, course.id)?;
+            sign_up_for_course(conn, student.id, course.id)?;
             Ok(())
         })
     }
@@ -154,8 +514,8 @@ mod tests {
             let student = students_db_functions::create_student(conn, new_student)?;
             let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
             let course = courses_db_functions::create_course(conn, new_course)?;
-            create_student_course(conn, student.id, course.id)?;
-            create_student_course(conn, student.id, course.id)?;
+            sign_up_for_course(conn, student.id, course.id)?;
+            sign_up_for_course(conn, student.id, course.id)?;
             Ok(())
         })
     }
@@ -168,8 +528,345 @@ mod tests {
             let student = students_db_functions::create_student(conn, new_student)?;
             let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
             let course = courses_db_functions::create_course(conn, new_course)?;
-            create_student_course(conn, student.id, course.id)?;
-            delete_student_course(conn, student.id, course.id)?;
+            let offering = course_offerings_db_functions::tests::open_offering(conn, course.id)?;
+            create_student_course(conn, student.id, &offering)?.unwrap();
+            delete_student_course(conn, student.id, &offering)?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_delete_student_courses_for_student() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("data science"), ..Default::default() };
+            let course_one = courses_db_functions::create_course(conn, new_course)?;
+            let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
+            let course_two = courses_db_functions::create_course(conn, new_course)?;
+            sign_up_for_course(conn, student.id, course_one.id)?;
+            sign_up_for_course(conn, student.id, course_two.id)?;
+
+            assert_eq!(2, delete_student_courses_for_student(conn, student.id)?);
+            assert!(get_courses_attended_by_student(conn, "some_user@gmail.com")?.is_empty());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_delete_student_courses_for_course() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student_one = students_db_functions::create_student(conn, new_student)?;
+            let new_student = NewStudent { email: String::from("some_user_two@gmail.com"), ..Default::default() };
+            let student_two = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            sign_up_for_course(conn, student_one.id, course.id)?;
+            sign_up_for_course(conn, student_two.id, course.id)?;
+
+            assert_eq!(2, delete_student_courses_for_course(conn, course.id)?);
+            assert!(get_students_in_course(conn, "machine learning")?.is_empty());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_create_student_course_waitlisted_when_full() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student_one = students_db_functions::create_student(conn, new_student)?;
+            let new_student = NewStudent { email: String::from("some_user_two@gmail.com"), ..Default::default() };
+            let student_two = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), capacity: Some(1), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+
+            assert!(matches!(sign_up_for_course(conn, student_one.id, course.id)?, EnrollmentOutcome::Enrolled(_)));
+            assert!(matches!(sign_up_for_course(conn, student_two.id, course.id)?, EnrollmentOutcome::Waitlisted(_)));
+            assert_eq!(vec![student_two.id], get_waitlist_for_course(conn, &course)?.into_iter().map(|(entry, _)| entry.student_id).collect::<Vec<i32>>());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_count_enrolled_and_waitlisted_by_course() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student_one = students_db_functions::create_student(conn, new_student)?;
+            let new_student = NewStudent { email: String::from("some_user_two@gmail.com"), ..Default::default() };
+            let student_two = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), capacity: Some(1), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let new_course = NewCourse { name: String::from("data science"), ..Default::default() };
+            let empty_course = courses_db_functions::create_course(conn, new_course)?;
+            let offering = course_offerings_db_functions::tests::open_offering(conn, course.id)?;
+            create_student_course(conn, student_one.id, &offering)?.unwrap();
+            create_student_course(conn, student_two.id, &offering)?.unwrap();
+
+            let counts_for = |counts: Vec<(String, i64)>| counts.into_iter().filter(|(name, _)| *name == course.name || *name == empty_course.name).collect::<Vec<(String, i64)>>();
+            assert_eq!(vec![(course.name.clone(), 1), (empty_course.name.clone(), 0)], counts_for(count_enrolled_by_course(conn)?));
+            assert_eq!(vec![(course.name.clone(), 1), (empty_course.name.clone(), 0)], counts_for(count_waitlisted_by_course(conn)?));
+
+            // the freed seat goes to the waitlisted student, dropped enrollments are not counted
+            update_student_course_status(conn, student_one.id, offering.id, EnrollmentStatus::Dropped)?;
+            assert_eq!(vec![(course.name.clone(), 1), (empty_course.name.clone(), 0)], counts_for(count_enrolled_by_course(conn)?));
+            assert_eq!(vec![(course.name.clone(), 0), (empty_course.name.clone(), 0)], counts_for(count_waitlisted_by_course(conn)?));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_delete_student_course_promotes_from_waitlist() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student_one = students_db_functions::create_student(conn, new_student)?;
+            let new_student = NewStudent { email: String::from("some_user_two@gmail.com"), ..Default::default() };
+            let student_two = students_db_functions::create_student(conn, new_student)?;
+            let new_student = NewStudent { email: String::from("some_user_three@gmail.com"), ..Default::default() };
+            let student_three = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), capacity: Some(1), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let offering = course_offerings_db_functions::tests::open_offering(conn, course.id)?;
+            create_student_course(conn, student_one.id, &offering)?.unwrap();
+            create_student_course(conn, student_two.id, &offering)?.unwrap();
+            create_student_course(conn, student_three.id, &offering)?.unwrap();
+
+            delete_student_course(conn, student_one.id, &offering)?;
+            let students_in_course = get_students_in_course(conn, "machine learning")?;
+            assert_eq!(vec!["some_user_two@gmail.com"], students_in_course.into_iter().map(|item| item.email).collect::<Vec<String>>());
+            assert_eq!(vec![student_three.id], get_waitlist_for_course(conn, &course)?.into_iter().map(|(entry, _)| entry.student_id).collect::<Vec<i32>>());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_promote_from_waitlist_skips_schedule_conflicts() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student_one = students_db_functions::create_student(conn, new_student)?;
+            let new_student = NewStudent { email: String::from("some_user_two@gmail.com"), ..Default::default() };
+            let student_two = students_db_functions::create_student(conn, new_student)?;
+            let new_student = NewStudent { email: String::from("some_user_three@gmail.com"), ..Default::default() };
+            let student_three = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), capacity: Some(1), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let new_course = NewCourse { name: String::from("data science"), ..Default::default() };
+            let clashing = courses_db_functions::create_course(conn, new_course)?;
+            let offering = course_offerings_db_functions::tests::open_offering(conn, course.id)?;
+            let clashing_offering = course_offerings_db_functions::tests::open_offering(conn, clashing.id)?;
+            for (offering, start, end) in [(&offering, 9, 10), (&clashing_offering, 10, 11)] {
+                let meeting = NewCourseMeeting {
+                    course_id: offering.course_id,
+                    offering_id: offering.id,
+                    day_of_week: DayOfWeek::Monday,
+                    start_time: NaiveTime::from_hms_opt(start, 0, 0).unwrap(),
+                    end_time: NaiveTime::from_hms_opt(end, 30, 0).unwrap(),
+                    room: None,
+                };
+                course_meetings_db_functions::create_course_meeting(conn, meeting)?;
+            }
+            create_student_course(conn, student_one.id, &offering)?.unwrap();
+            create_student_course(conn, student_two.id, &offering)?.unwrap();
+            create_student_course(conn, student_three.id, &offering)?.unwrap();
+            create_student_course(conn, student_two.id, &clashing_offering)?.unwrap();
+
+            // the first in line now has a clashing class, so the seat goes to the next one
+            update_student_course_status(conn, student_one.id, offering.id, EnrollmentStatus::Dropped)?;
+            let enrolled = get_enrollments_for_course(conn, &course, Some(EnrollmentStatus::Enrolled))?;
+            assert_eq!(vec![student_three.id], enrolled.into_iter().map(|(_, student)| student.id).collect::<Vec<i32>>());
+            assert_eq!(vec![student_two.id], get_waitlist_for_course(conn, &course)?.into_iter().map(|(entry, _)| entry.student_id).collect::<Vec<i32>>());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_update_student_course_status() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let offering = course_offerings_db_functions::tests::open_offering(conn, course.id)?;
+            create_student_course(conn, student.id, &offering)?.unwrap();
+
+            let enrollment = update_student_course_status(conn, student.id, offering.id, EnrollmentStatus::Dropped)?;
+            assert_eq!(EnrollmentStatus::Dropped, enrollment.status);
+            assert!(get_enrollments_for_course(conn, &course, None)?.is_empty());
+            assert_eq!(1, get_enrollments_for_course(conn, &course, Some(EnrollmentStatus::Dropped))?.len());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_create_student_course_after_drop_reenrolls() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let offering = course_offerings_db_functions::tests::open_offering(conn, course.id)?;
+            create_student_course(conn, student.id, &offering)?.unwrap();
+            update_student_course_status(conn, student.id, offering.id, EnrollmentStatus::Dropped)?;
+
+            match create_student_course(conn, student.id, &offering)?.unwrap() {
+                EnrollmentOutcome::Enrolled(enrollment) => assert_eq!(EnrollmentStatus::Enrolled, enrollment.status),
+                EnrollmentOutcome::Waitlisted(_) => panic!("expected enrollment"),
+            }
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_dropped_enrollment_frees_seat() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student_one = students_db_functions::create_student(conn, new_student)?;
+            let new_student = NewStudent { email: String::from("some_user_two@gmail.com"), ..Default::default() };
+            let student_two = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), capacity: Some(1), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let offering = course_offerings_db_functions::tests::open_offering(conn, course.id)?;
+            create_student_course(conn, student_one.id, &offering)?.unwrap();
+            create_student_course(conn, student_two.id, &offering)?.unwrap();
+
+            update_student_course_status(conn, student_one.id, offering.id, EnrollmentStatus::Dropped)?;
+            assert_eq!(Some(EnrollmentStatus::Enrolled), get_student_course(conn, student_two.id, offering.id)?.map(|x| x.status));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_record_grade() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("data science"), ..Default::default() };
+            let course_one = courses_db_functions::create_course(conn, new_course)?;
+            let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
+            let course_two = courses_db_functions::create_course(conn, new_course)?;
+            let offering_one = course_offerings_db_functions::tests::open_offering(conn, course_one.id)?;
+            let offering_two = course_offerings_db_functions::tests::open_offering(conn, course_two.id)?;
+            create_student_course(conn, student.id, &offering_one)?.unwrap();
+            create_student_course(conn, student.id, &offering_two)?.unwrap();
+
+            let enrollment = record_grade(conn, student.id, offering_one.id, Grade::BPlus)?;
+            assert_eq!((EnrollmentStatus::Completed, Some(Grade::BPlus)), (enrollment.status, enrollment.grade));
+            let enrollment = record_grade(conn, student.id, offering_two.id, Grade::F)?;
+            assert_eq!((EnrollmentStatus::Failed, Some(Grade::F)), (enrollment.status, enrollment.grade));
+
+            let graded = get_courses_attended_by_student(conn, "some_user@gmail.com")?;
+            assert_eq!(vec!["data science", "machine learning"], graded.into_iter().map(|(_, c)| c.name).collect::<Vec<String>>());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_record_grade_promotes_from_waitlist() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student_one = students_db_functions::create_student(conn, new_student)?;
+            let new_student = NewStudent { email: String::from("some_user_two@gmail.com"), ..Default::default() };
+            let student_two = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), capacity: Some(1), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let offering = course_offerings_db_functions::tests::open_offering(conn, course.id)?;
+            create_student_course(conn, student_one.id, &offering)?.unwrap();
+            create_student_course(conn, student_two.id, &offering)?.unwrap();
+
+            record_grade(conn, student_one.id, offering.id, Grade::A)?;
+            assert_eq!(Some(EnrollmentStatus::Enrolled), get_student_course(conn, student_two.id, offering.id)?.map(|x| x.status));
+            assert!(get_waitlist_for_course(conn, &course)?.is_empty());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_create_student_course_after_fail_clears_grade() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let offering = course_offerings_db_functions::tests::open_offering(conn, course.id)?;
+            create_student_course(conn, student.id, &offering)?.unwrap();
+            record_grade(conn, student.id, offering.id, Grade::F)?;
+
+            match create_student_course(conn, student.id, &offering)?.unwrap() {
+                EnrollmentOutcome::Enrolled(enrollment) => assert_eq!((EnrollmentStatus::Enrolled, None), (enrollment.status, enrollment.grade)),
+                EnrollmentOutcome::Waitlisted(_) => panic!("expected enrollment"),
+            }
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_offerings_keep_separate_seats() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student_one = students_db_functions::create_student(conn, new_student)?;
+            let new_student = NewStudent { email: String::from("some_user_two@gmail.com"), ..Default::default() };
+            let student_two = students_db_functions::create_student(conn, new_student)?;
+            let new_student = NewStudent { email: String::from("some_user_three@gmail.com"), ..Default::default() };
+            let student_three = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let new_term = NewTerm { name: String::from("fall 2026"), starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 12, 18).unwrap() };
+            let term = terms_db_functions::create_term(conn, new_term)?;
+            let section_one = course_offerings_db_functions::create_course_offering(conn, NewCourseOffering { course_id: course.id, term_id: term.id, section: Some(String::from("001")), capacity: Some(1) })?;
+            let section_two = course_offerings_db_functions::create_course_offering(conn, NewCourseOffering { course_id: course.id, term_id: term.id, section: Some(String::from("002")), capacity: Some(1) })?;
+
+            assert!(matches!(create_student_course(conn, student_one.id, &section_one)?.unwrap(), EnrollmentOutcome::Enrolled(_)));
+            assert!(matches!(create_student_course(conn, student_two.id, &section_one)?.unwrap(), EnrollmentOutcome::Waitlisted(_)));
+            match create_student_course(conn, student_three.id, &section_two)?.unwrap() {
+                EnrollmentOutcome::Enrolled(enrollment) => assert_eq!(section_two.id, enrollment.offering_id),
+                EnrollmentOutcome::Waitlisted(_) => panic!("expected enrollment"),
+            }
+
+            update_student_course_status(conn, student_one.id, section_one.id, EnrollmentStatus::Dropped)?;
+            assert_eq!(Some(EnrollmentStatus::Enrolled), get_student_course(conn, student_two.id, section_one.id)?.map(|x| x.status));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_retake_course_in_later_term() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_student = NewStudent { email: String::from("some_user@gmail.com"), ..Default::default() };
+            let student = students_db_functions::create_student(conn, new_student)?;
+            let new_course = NewCourse { name: String::from("machine learning"), ..Default::default() };
+            let course = courses_db_functions::create_course(conn, new_course)?;
+            let new_term = NewTerm { name: String::from("fall 2026"), starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 12, 18).unwrap() };
+            let fall = terms_db_functions::create_term(conn, new_term)?;
+            let new_term = NewTerm { name: String::from("spring 2027"), starts_on: NaiveDate::from_ymd_opt(2027, 1, 11).unwrap(), ends_on: NaiveDate::from_ymd_opt(2027, 5, 7).unwrap() };
+            let spring = terms_db_functions::create_term(conn, new_term)?;
+            let fall_section = course_offerings_db_functions::create_course_offering(conn, NewCourseOffering { course_id: course.id, term_id: fall.id, ..Default::default() })?;
+            let second_fall_section = course_offerings_db_functions::create_course_offering(conn, NewCourseOffering { course_id: course.id, term_id: fall.id, section: Some(String::from("002")), ..Default::default() })?;
+            let spring_section = course_offerings_db_functions::create_course_offering(conn, NewCourseOffering { course_id: course.id, term_id: spring.id, ..Default::default() })?;
+
+            create_student_course(conn, student.id, &fall_section)?.unwrap();
+            assert!(create_student_course(conn, student.id, &second_fall_section).is_err());
+            record_grade(conn, student.id, fall_section.id, Grade::F)?;
+            create_student_course(conn, student.id, &spring_section)?.unwrap();
+            record_grade(conn, student.id, spring_section.id, Grade::B)?;
+
+            // both attempts are kept, each with its own offering
+            let graded = get_courses_attended_by_student(conn, "some_user@gmail.com")?;
+            assert_eq!(vec![(fall_section.id, Some(Grade::F)), (spring_section.id, Some(Grade::B))], graded.into_iter().map(|(sc, _)| (sc.offering_id, sc.grade)).collect::<Vec<_>>());
+            assert_eq!(Some(spring_section.id), get_current_student_course(conn, student.id, course.id)?.map(|x| x.offering_id));
             Ok(())
         })
     }
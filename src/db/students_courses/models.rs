@@ -1,15 +1,199 @@
-use diesel::{Associations, Identifiable, Queryable, Selectable};
+use chrono::NaiveDateTime;
+use diesel::{AsExpression, Associations, FromSqlRow, Identifiable, Queryable, Selectable};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
 
+use crate::db::course_meetings::models::ScheduleConflict;
 use crate::db::courses::models::Course;
-use crate::schema::students_courses;
+use crate::schema::{students_courses, waitlist_entries};
 use crate::db::students::models::Student;
 
-#[derive(Identifiable, Selectable, Queryable, Associations, Debug, Clone)]
+#[derive(Identifiable, Selectable, Queryable, Associations, Serialize, Debug, Clone)]
 #[diesel(belongs_to(Student))]
 #[diesel(belongs_to(Course))]
 #[diesel(table_name = students_courses)]
-#[diesel(primary_key(student_id, course_id))]
+#[diesel(primary_key(student_id, offering_id))]
 pub struct StudentCourse {
     pub student_id: i32,
     pub course_id: i32,
-}
\ No newline at end of file
+    pub status: EnrollmentStatus,
+    pub enrolled_at: NaiveDateTime,
+    pub status_changed_at: NaiveDateTime,
+    pub grade: Option<Grade>,
+    pub offering_id: i32,
+}
+
+#[derive(AsExpression, FromSqlRow, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum EnrollmentStatus {
+    Enrolled,
+    Dropped,
+    Completed,
+    Failed,
+}
+
+impl EnrollmentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EnrollmentStatus::Enrolled => "enrolled",
+            EnrollmentStatus::Dropped => "dropped",
+            EnrollmentStatus::Completed => "completed",
+            EnrollmentStatus::Failed => "failed",
+        }
+    }
+
+    // only an active enrollment can change; re-enrolling after a drop or fail goes through sign up again
+    pub fn can_transition_to(&self, next: EnrollmentStatus) -> bool {
+        matches!(
+            (self, next),
+            (EnrollmentStatus::Enrolled, EnrollmentStatus::Dropped)
+                | (EnrollmentStatus::Enrolled, EnrollmentStatus::Completed)
+                | (EnrollmentStatus::Enrolled, EnrollmentStatus::Failed)
+        )
+    }
+
+    // a recorded grade can still be corrected, which may move a completed course to failed or back
+    pub fn can_record_grade(&self, grade: Grade) -> bool {
+        self.can_transition_to(grade.status()) || matches!(self, EnrollmentStatus::Completed | EnrollmentStatus::Failed)
+    }
+}
+
+impl ToSql<Text, Pg> for EnrollmentStatus {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), &mut out.reborrow())
+    }
+}
+
+impl FromSql<Text, Pg> for EnrollmentStatus {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+            "enrolled" => Ok(EnrollmentStatus::Enrolled),
+            "dropped" => Ok(EnrollmentStatus::Dropped),
+            "completed" => Ok(EnrollmentStatus::Completed),
+            "failed" => Ok(EnrollmentStatus::Failed),
+            other => Err(format!("unrecognized enrollment status: {}", other).into()),
+        }
+    }
+}
+
+#[derive(AsExpression, FromSqlRow, Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[diesel(sql_type = Text)]
+pub enum Grade {
+    #[serde(rename = "A")]
+    A,
+    #[serde(rename = "A-")]
+    AMinus,
+    #[serde(rename = "B+")]
+    BPlus,
+    #[serde(rename = "B")]
+    B,
+    #[serde(rename = "B-")]
+    BMinus,
+    #[serde(rename = "C+")]
+    CPlus,
+    #[serde(rename = "C")]
+    C,
+    #[serde(rename = "C-")]
+    CMinus,
+    #[serde(rename = "D+")]
+    DPlus,
+    #[serde(rename = "D")]
+    D,
+    #[serde(rename = "D-")]
+    DMinus,
+    #[serde(rename = "F")]
+    F,
+}
+
+impl Grade {
+    const ALL: [Grade; 12] = [
+        Grade::A, Grade::AMinus, Grade::BPlus, Grade::B, Grade::BMinus, Grade::CPlus,
+        Grade::C, Grade::CMinus, Grade::DPlus, Grade::D, Grade::DMinus, Grade::F,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Grade::A => "A",
+            Grade::AMinus => "A-",
+            Grade::BPlus => "B+",
+            Grade::B => "B",
+            Grade::BMinus => "B-",
+            Grade::CPlus => "C+",
+            Grade::C => "C",
+            Grade::CMinus => "C-",
+            Grade::DPlus => "D+",
+            Grade::D => "D",
+            Grade::DMinus => "D-",
+            Grade::F => "F",
+        }
+    }
+
+    pub fn points(&self) -> f64 {
+        match self {
+            Grade::A => 4.0,
+            Grade::AMinus => 3.7,
+            Grade::BPlus => 3.3,
+            Grade::B => 3.0,
+            Grade::BMinus => 2.7,
+            Grade::CPlus => 2.3,
+            Grade::C => 2.0,
+            Grade::CMinus => 1.7,
+            Grade::DPlus => 1.3,
+            Grade::D => 1.0,
+            Grade::DMinus => 0.7,
+            Grade::F => 0.0,
+        }
+    }
+
+    pub fn is_passing(&self) -> bool {
+        *self != Grade::F
+    }
+
+    pub fn status(&self) -> EnrollmentStatus {
+        if self.is_passing() { EnrollmentStatus::Completed } else { EnrollmentStatus::Failed }
+    }
+}
+
+impl ToSql<Text, Pg> for Grade {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), &mut out.reborrow())
+    }
+}
+
+impl FromSql<Text, Pg> for Grade {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+        Grade::ALL.into_iter()
+            .find(|grade| grade.as_str() == value)
+            .ok_or_else(|| format!("unrecognized grade: {}", value).into())
+    }
+}
+
+#[derive(Identifiable, Selectable, Queryable, Associations, Serialize, Debug, Clone)]
+#[diesel(belongs_to(Student))]
+#[diesel(belongs_to(Course))]
+#[diesel(table_name = waitlist_entries)]
+pub struct WaitlistEntry {
+    pub id: i32,
+    pub student_id: i32,
+    pub course_id: i32,
+    pub created_at: NaiveDateTime,
+    pub offering_id: i32,
+}
+
+#[derive(Debug, Clone)]
+pub enum EnrollmentOutcome {
+    Enrolled(StudentCourse),
+    Waitlisted(WaitlistEntry),
+}
+
+// checked inside the sign-up transaction, so nothing is written when the student is turned away
+#[derive(Debug, Clone)]
+pub enum SignUpRejection {
+    MissingPrerequisites(Vec<Course>),
+    ScheduleConflict(Vec<ScheduleConflict>),
+}
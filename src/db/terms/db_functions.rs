@@ -0,0 +1,96 @@
+use chrono::NaiveDate;
+use diesel::prelude::*;
+
+use crate::db::terms::models::{NewTerm, Term};
+use crate::schema::terms;
+
+pub fn create_term(conn: &mut PgConnection, new_term: NewTerm) -> QueryResult<Term> {
+    diesel::insert_into(terms::table)
+        .values(&new_term)
+        .returning(Term::as_returning())
+        .get_result(conn)
+}
+
+pub fn get_term(conn: &mut PgConnection, term_id: i32) -> QueryResult<Term> {
+    terms::table.find(term_id)
+        .select(Term::as_select())
+        .first(conn)
+}
+
+// terms may overlap, the one that started last is taken as the current one
+pub fn get_current_term(conn: &mut PgConnection, today: NaiveDate) -> QueryResult<Option<Term>> {
+    terms::table
+        .filter(terms::starts_on.le(today))
+        .filter(terms::ends_on.ge(today))
+        .order((terms::starts_on.desc(), terms::id.desc()))
+        .select(Term::as_select())
+        .first(conn)
+        .optional()
+}
+
+pub fn set_registration_open(conn: &mut PgConnection, term_id: i32, open: bool) -> QueryResult<Term> {
+    diesel::update(terms::table.find(term_id))
+        .set(terms::registration_open.eq(open))
+        .returning(Term::as_returning())
+        .get_result(conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::{Connection, result::Error};
+
+    use crate::db;
+
+    use super::*;
+
+    #[test]
+    fn test_create_term() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_term = NewTerm { name: String::from("fall 2026"), starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 12, 18).unwrap() };
+            let term = create_term(conn, new_term)?;
+            assert_eq!(term, get_term(conn, term.id)?);
+            assert!(!term.registration_open);
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_create_term_ends_before_start() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_term = NewTerm { name: String::from("fall 2026"), starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 8, 1).unwrap() };
+            create_term(conn, new_term)?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_current_term() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_term = NewTerm { name: String::from("fall 2030"), starts_on: NaiveDate::from_ymd_opt(2030, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2030, 12, 18).unwrap() };
+            let fall = create_term(conn, new_term)?;
+            let new_term = NewTerm { name: String::from("autumn 2030 intensive"), starts_on: NaiveDate::from_ymd_opt(2030, 10, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2030, 10, 31).unwrap() };
+            let intensive = create_term(conn, new_term)?;
+            assert_eq!(Some(fall.clone()), get_current_term(conn, NaiveDate::from_ymd_opt(2030, 9, 15).unwrap())?);
+            assert_eq!(Some(intensive), get_current_term(conn, NaiveDate::from_ymd_opt(2030, 10, 15).unwrap())?);
+            assert_eq!(Some(fall), get_current_term(conn, NaiveDate::from_ymd_opt(2030, 12, 18).unwrap())?);
+            assert_eq!(None, get_current_term(conn, NaiveDate::from_ymd_opt(2031, 2, 1).unwrap())?);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_set_registration_open() {
+        let mut conn = db::establish_connection();
+        conn.test_transaction::<_, Error, _>(|conn| {
+            let new_term = NewTerm { name: String::from("fall 2026"), starts_on: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), ends_on: NaiveDate::from_ymd_opt(2026, 12, 18).unwrap() };
+            let term = create_term(conn, new_term)?;
+            assert!(set_registration_open(conn, term.id, true)?.registration_open);
+            assert!(!set_registration_open(conn, term.id, false)?.registration_open);
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::NaiveDate;
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+
+use crate::api::terms::types::CreateTermRequest;
+use crate::schema::terms;
+
+#[derive(Identifiable, Queryable, Selectable, PartialEq, Debug, Clone)]
+#[diesel(table_name = terms)]
+pub struct Term {
+    pub id: i32,
+    pub name: String,
+    pub starts_on: NaiveDate,
+    pub ends_on: NaiveDate,
+    pub registration_open: bool,
+}
+
+#[derive(Insertable, Clone, Default)]
+#[diesel(table_name = terms)]
+pub struct NewTerm {
+    pub name: String,
+    pub starts_on: NaiveDate,
+    pub ends_on: NaiveDate,
+}
+
+impl From<CreateTermRequest> for NewTerm {
+    fn from(value: CreateTermRequest) -> Self {
+        Self {
+            name: value.name,
+            starts_on: value.starts_on,
+            ends_on: value.ends_on,
+        }
+    }
+}
@@ -1,14 +1,24 @@
 use std::env;
+use std::sync::Arc;
 
 use actix_web::{App, HttpServer, web};
 use diesel::PgConnection;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use dotenvy::dotenv;
 
+use crate::api::audit::handlers::audit_api_scope;
+use crate::api::auth::{auth_api_scope, AuthConfig, Authentication};
 use crate::api::courses::handlers::courses_api_scope;
+use crate::api::health::handlers::health_api_config;
+use crate::api::instructors::handlers::instructors_api_scope;
+use crate::api::metrics::handlers::metrics_api_scope;
+use crate::api::metrics::middleware::RequestMetrics;
+use crate::api::request_id::RequestId;
+use crate::api::search::handlers::search_api_scope;
 use crate::api::students::handlers::student_api_scope;
 use crate::api::students_courses::handlers::students_courses_api_scope;
-use crate::db::initialize_db_pool;
+use crate::api::terms::handlers::terms_api_scope;
+use crate::db::{build_db_pool, PoolConfig, wait_for_connection};
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
 
@@ -35,14 +45,26 @@ fn get_binding_address<'a>() -> &'a str {
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     let binding_address = get_binding_address();
-    let pool = initialize_db_pool();
-    run_migration(&mut pool.clone().get().unwrap());
+    let pool_config = PoolConfig::from_env();
+    let pool = build_db_pool(&pool_config);
+    run_migration(&mut wait_for_connection(&pool, &pool_config).await.expect("database should become reachable during startup"));
+    let auth_config = Arc::new(AuthConfig::from_env());
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(pool.clone()))
+            .wrap(Authentication::new(auth_config.clone()))
+            .wrap(RequestId)
+            .wrap(RequestMetrics)
+            .configure(health_api_config)
+            .service(auth_api_scope())
             .service(student_api_scope())
             .service(courses_api_scope())
             .service(students_courses_api_scope())
+            .service(terms_api_scope())
+            .service(instructors_api_scope())
+            .service(search_api_scope())
+            .service(audit_api_scope())
+            .service(metrics_api_scope())
     })
         .bind((binding_address, 8080))?
         .run()
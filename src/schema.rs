@@ -1,10 +1,84 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    api_keys (id) {
+        id -> Int4,
+        name -> Varchar,
+        #[max_length = 64]
+        key_hash -> Varchar,
+        created_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+        role -> Varchar,
+    }
+}
+
+diesel::table! {
+    audit_events (id) {
+        id -> Int4,
+        actor -> Nullable<Varchar>,
+        action -> Varchar,
+        entity_type -> Varchar,
+        entity_id -> Varchar,
+        before -> Nullable<Jsonb>,
+        after -> Nullable<Jsonb>,
+        request_id -> Nullable<Varchar>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    course_instructors (course_id, instructor_id) {
+        course_id -> Int4,
+        instructor_id -> Int4,
+    }
+}
+
+diesel::table! {
+    course_meetings (id) {
+        id -> Int4,
+        course_id -> Int4,
+        day_of_week -> Varchar,
+        start_time -> Time,
+        end_time -> Time,
+        room -> Nullable<Varchar>,
+        offering_id -> Int4,
+    }
+}
+
+diesel::table! {
+    course_offerings (id) {
+        id -> Int4,
+        course_id -> Int4,
+        term_id -> Int4,
+        section -> Varchar,
+        capacity -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    course_prerequisites (course_id, prerequisite_id) {
+        course_id -> Int4,
+        prerequisite_id -> Int4,
+    }
+}
+
 diesel::table! {
     courses (id) {
         id -> Int4,
         name -> Varchar,
         course_desc -> Nullable<Varchar>,
+        capacity -> Nullable<Int4>,
+        credits -> Int4,
+    }
+}
+
+diesel::table! {
+    instructors (id) {
+        id -> Int4,
+        name -> Varchar,
+        email -> Varchar,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
     }
 }
 
@@ -19,17 +93,62 @@ diesel::table! {
 }
 
 diesel::table! {
-    students_courses (student_id, course_id) {
+    students_courses (student_id, offering_id) {
+        student_id -> Int4,
+        course_id -> Int4,
+        status -> Varchar,
+        enrolled_at -> Timestamp,
+        status_changed_at -> Timestamp,
+        grade -> Nullable<Varchar>,
+        offering_id -> Int4,
+    }
+}
+
+diesel::table! {
+    terms (id) {
+        id -> Int4,
+        name -> Varchar,
+        starts_on -> Date,
+        ends_on -> Date,
+        registration_open -> Bool,
+    }
+}
+
+diesel::table! {
+    waitlist_entries (id) {
+        id -> Int4,
         student_id -> Int4,
         course_id -> Int4,
+        created_at -> Timestamp,
+        offering_id -> Int4,
     }
 }
 
+diesel::joinable!(course_instructors -> courses (course_id));
+diesel::joinable!(course_instructors -> instructors (instructor_id));
+diesel::joinable!(course_meetings -> course_offerings (offering_id));
+diesel::joinable!(course_meetings -> courses (course_id));
+diesel::joinable!(course_offerings -> courses (course_id));
+diesel::joinable!(course_offerings -> terms (term_id));
+diesel::joinable!(course_prerequisites -> courses (prerequisite_id));
+diesel::joinable!(students_courses -> course_offerings (offering_id));
 diesel::joinable!(students_courses -> courses (course_id));
 diesel::joinable!(students_courses -> students (student_id));
+diesel::joinable!(waitlist_entries -> course_offerings (offering_id));
+diesel::joinable!(waitlist_entries -> courses (course_id));
+diesel::joinable!(waitlist_entries -> students (student_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_keys,
+    audit_events,
+    course_instructors,
+    course_meetings,
+    course_offerings,
+    course_prerequisites,
     courses,
+    instructors,
     students,
     students_courses,
+    terms,
+    waitlist_entries,
 );